@@ -5,6 +5,7 @@ window.addEventListener("DOMContentLoaded", async () => {
   registerMessageEvent();
   window.selectWorld = selectWorld;
   window.startGeneration = startGeneration;
+  window.undoGeneration = undoGeneration;
   setupProgressListener();
   initSettings();
   initWorldPicker();
@@ -53,6 +54,11 @@ async function applyLocalization(localization) {
     startButtonElement.textContent = localization.start_generation;
   }
 
+  const undoButtonElement = document.getElementById("undo-button");
+  if (undoButtonElement && localization.undo_generation) {
+    undoButtonElement.title = localization.undo_generation;
+  }
+
   const progressElement = document.querySelector("h2[data-localize='progress']");
   if (progressElement) {
     progressElement.textContent = localization.progress;
@@ -103,6 +109,13 @@ async function applyLocalization(localization) {
     groundLevelLabel.textContent = localization.ground_level;
   }
 
+  for (const key of ["game_mode", "difficulty", "time_of_day", "world_preset", "daylight_cycle", "mob_spawning", "weather_cycle"]) {
+    const worldSettingLabel = document.querySelector(`label[data-localize='${key}']`);
+    if (worldSettingLabel && localization[key]) {
+      worldSettingLabel.textContent = localization[key];
+    }
+  }
+
   const footerLinkElement = document.querySelector(".footer-link");
   if (footerLinkElement) {
     footerLinkElement.innerHTML = localization.footer_text.replace("{year}", '<span id="current-year"></span>').replace("{version}", '<span id="version-placeholder"></span>');
@@ -220,6 +233,17 @@ function setupProgressListener() {
       }
     }
   });
+
+  // Show the rendered preview once the world is saved
+  const previewImage = document.getElementById("preview-image");
+  window.__TAURI__.event.listen("preview-ready", (event) => {
+    const previewBlob = new Blob([new Uint8Array(event.payload)], { type: "image/png" });
+    if (previewImage.src) {
+      URL.revokeObjectURL(previewImage.src);
+    }
+    previewImage.src = URL.createObjectURL(previewBlob);
+    previewImage.style.display = "block";
+  });
 }
 
 function initSettings() {
@@ -421,6 +445,32 @@ function handleWorldSelectionError(errorCode) {
 }
 
 let generationButtonEnabled = true;
+
+// Restores the world from the backup made by the last generation
+async function undoGeneration() {
+  if (generationButtonEnabled === false) {
+    return;
+  }
+
+  if (!worldPath || worldPath === "") {
+    document.getElementById('selected-world').textContent = window.localization.select_minecraft_world_first;
+    document.getElementById('selected-world').style.color = "#fa7878";
+    return;
+  }
+
+  const progressMessage = document.getElementById("progress-message");
+  try {
+    await invoke("gui_undo_generation", { selectedWorld: worldPath });
+    progressMessage.textContent = window.localization.generation_undone || "The last generation was undone.";
+    progressMessage.style.color = "#7bd864";
+    document.getElementById("preview-image").style.display = "none";
+  } catch (error) {
+    console.error("Error undoing generation:", error);
+    progressMessage.textContent = `Error! ${error}`;
+    progressMessage.style.color = "#fa7878";
+  }
+}
+
 async function startGeneration() {
   try {
     if (generationButtonEnabled === false) {
@@ -443,6 +493,13 @@ async function startGeneration() {
     var scale = parseFloat(document.getElementById("scale-value-slider").value);
     var floodfill_timeout = parseInt(document.getElementById("floodfill-timeout").value, 10);
     var ground_level = parseInt(document.getElementById("ground-level").value, 10);
+    var game_mode = document.getElementById("game-mode").value;
+    var difficulty = document.getElementById("difficulty").value;
+    var time_of_day = document.getElementById("time-of-day").value;
+    var world_preset = document.getElementById("world-preset").value;
+    var daylight_cycle = document.getElementById("daylight-cycle-toggle").checked;
+    var mob_spawning = document.getElementById("mob-spawning-toggle").checked;
+    var weather_cycle = document.getElementById("weather-cycle-toggle").checked;
 
     // Validate floodfill_timeout and ground_level
     floodfill_timeout = isNaN(floodfill_timeout) || floodfill_timeout < 0 ? 20 : floodfill_timeout;
@@ -456,10 +513,18 @@ async function startGeneration() {
         groundLevel: ground_level,
         winterMode: winter_mode,
         floodfillTimeout: floodfill_timeout,
+        gameMode: game_mode,
+        difficulty: difficulty,
+        timeOfDay: time_of_day,
+        daylightCycle: daylight_cycle,
+        mobSpawning: mob_spawning,
+        weatherCycle: weather_cycle,
+        worldPreset: world_preset,
     });
 
     console.log("Generation process started.");
     generationButtonEnabled = false;
+    document.getElementById("preview-image").style.display = "none";
   } catch (error) {
     console.error("Error starting generation:", error);
     generationButtonEnabled = true;
@@ -1,3 +1,7 @@
+use crate::level_settings::{parse_time_of_day, Difficulty, GameMode, WorldPreset};
+use crate::minecraft_version::MinecraftVersion;
+use crate::style::Style;
+use crate::world_editor::bedrock;
 use clap::{ArgGroup, Parser};
 use colored::Colorize;
 use std::path::Path;
@@ -33,14 +37,86 @@ pub struct Args {
     #[arg(long, default_value = "1.0")]
     pub scale: f64,
 
+    /// Vertical scale to use, in blocks per meter (default: same as --scale)
+    #[arg(long, value_parser = parse_scale)]
+    pub vertical_scale: Option<f64>,
+
+    /// Style file mapping tags to blocks, widths and heights, in TOML or JSON (optional)
+    #[arg(long)]
+    pub style: Option<String>,
+
     /// Ground level to use in the Minecraft world
     #[arg(long, default_value_t = -62)]
     pub ground_level: i32,
 
+    /// Depth of the dirt below the surface block
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(i32).range(0..))]
+    pub topsoil_depth: i32,
+
+    /// Fill the ground below the topsoil with stone and deepslate down to bedrock at the bottom of the world (optional)
+    #[arg(long, default_value_t = false)]
+    pub fill_underground: bool,
+
     /// Enable winter mode (default: false)
     #[arg(long, default_value_t = false)]
     pub winter: bool,
 
+    /// Write a Bedrock Edition world to the path, a new or empty directory, instead of a Java Edition world (optional)
+    #[arg(long, default_value_t = false)]
+    pub bedrock: bool,
+
+    /// Minecraft Java Edition version to generate the world for, currently only 1.21.4 (default: latest supported)
+    #[arg(long, default_value_t = MinecraftVersion::default())]
+    pub mc_version: MinecraftVersion,
+
+    /// Spawn point as block coordinates x,z (default: center of the generated area)
+    #[arg(long, allow_hyphen_values = true, value_parser = parse_block_position)]
+    pub spawn_point: Option<(i32, i32)>,
+
+    /// Spawn point as a coordinate lat,lng inside the bounding box (optional)
+    #[arg(long, allow_hyphen_values = true, value_parser = parse_lat_lng, conflicts_with_all = ["spawn_point", "spawn_name"])]
+    pub spawn: Option<(f64, f64)>,
+
+    /// Spawn at the OSM feature with this name, e.g. "Central Station" (optional)
+    #[arg(long, conflicts_with = "spawn_point")]
+    pub spawn_name: Option<String>,
+
+    /// Game mode of the world (optional)
+    #[arg(long, value_enum)]
+    pub gamemode: Option<GameMode>,
+
+    /// Difficulty of the world (optional)
+    #[arg(long, value_enum)]
+    pub difficulty: Option<Difficulty>,
+
+    /// Time of day in ticks, or day, noon, sunset, night or midnight (optional)
+    #[arg(long, value_parser = parse_time_of_day)]
+    pub time: Option<i64>,
+
+    /// Whether the time of day advances (optional)
+    #[arg(long)]
+    pub daylight_cycle: Option<bool>,
+
+    /// Whether mobs spawn naturally (optional)
+    #[arg(long)]
+    pub mob_spawning: Option<bool>,
+
+    /// Whether the weather changes (optional)
+    #[arg(long)]
+    pub weather_cycle: Option<bool>,
+
+    /// Terrain outside the generated area, flat matches the ground layer (optional)
+    #[arg(long, value_enum)]
+    pub world_preset: Option<WorldPreset>,
+
+    /// Process everything but write nothing, then print a summary of what would be written (optional)
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+
+    /// Do not back up the world files the generation overwrites (optional)
+    #[arg(long, default_value_t = false)]
+    pub no_backup: bool,
+
     /// Enable debug mode (optional)
     #[arg(long, default_value_t = false, action = clap::ArgAction::SetTrue)]
     pub debug: bool,
@@ -48,13 +124,29 @@ pub struct Args {
     /// Set floodfill timeout (seconds) (optional)
     #[arg(long, value_parser = parse_duration)]
     pub timeout: Option<Duration>,
+
+    /// Render a top-down PNG preview of the generated area to this file (optional)
+    #[arg(long)]
+    pub preview: Option<String>,
+
+    /// Shade the preview by terrain height like in-game maps (optional)
+    #[arg(long, default_value_t = false, requires = "preview")]
+    pub preview_shading: bool,
+
+    /// Also write the generation report of handled and unhandled tags as JSON to this file (optional)
+    #[arg(long)]
+    pub report_json: Option<String>,
+
+    /// Also export the generated area as a schematic (.schem or .litematic), can be repeated (optional)
+    #[arg(long)]
+    pub export: Vec<String>,
 }
 
 impl Args {
     pub fn run(&self) {
         // Validating the world path
         let mc_world_path: &Path = Path::new(&self.path);
-        if !mc_world_path.join("region").exists() {
+        if !self.bedrock && !self.dry_run && !mc_world_path.join("region").exists() {
             eprintln!(
                 "{}",
                 "Error! No Minecraft world found at the given path"
@@ -64,14 +156,72 @@ impl Args {
             exit(1);
         }
 
+        // Validating that a Bedrock world does not overwrite another world
+        if self.bedrock && !self.dry_run {
+            if let Err(e) = bedrock::check_target(mc_world_path) {
+                eprintln!("{}", format!("Error! {}", e).red().bold());
+                exit(1);
+            }
+        }
+
+        // Validating the schematic export paths
+        for export in &self.export {
+            if !export.ends_with(".schem") && !export.ends_with(".litematic") {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Error! Unsupported export format: {} (expected .schem or .litematic)",
+                        export
+                    )
+                    .red()
+                    .bold()
+                );
+                exit(1);
+            }
+        }
+
+        // Validating the ground level against the build height, leaving room above the surface
+        if let Err(e) = self.mc_version.check_ground_level(self.ground_level) {
+            eprintln!("{}", format!("Error! {}", e).red().bold());
+            exit(1);
+        }
+
+        // Validating the style file
+        if let Err(e) = Style::load(self) {
+            eprintln!("{}", format!("Error! {}", e).red().bold());
+            exit(1);
+        }
+
         // Validating bbox if provided
         if let Some(bbox) = &self.bbox {
             if !validate_bounding_box(bbox) {
                 eprintln!("{}", "Error! Invalid bbox input".red().bold());
                 exit(1);
             }
+
+            // Validating the spawn coordinate against the bbox
+            if let Some((lat, lng)) = self.spawn {
+                let parts: Vec<f64> = bbox
+                    .split(',')
+                    .map(|part: &str| part.parse::<f64>().unwrap_or(0.0))
+                    .collect();
+                if !(parts[0]..=parts[2]).contains(&lng) || !(parts[1]..=parts[3]).contains(&lat) {
+                    eprintln!(
+                        "{}",
+                        "Error! The spawn coordinate is outside the bounding box"
+                            .red()
+                            .bold()
+                    );
+                    exit(1);
+                }
+            }
         }
     }
+
+    /// Blocks per meter of heights, the horizontal scale unless a vertical one is given.
+    pub fn vertical_scale(&self) -> f64 {
+        self.vertical_scale.unwrap_or(self.scale)
+    }
 }
 
 /// Validates the bounding box string
@@ -97,7 +247,60 @@ fn validate_bounding_box(bbox: &str) -> bool {
     min_lng < max_lng && min_lat < max_lat
 }
 
+/// Parses a scale, which has to be greater than 0.
+fn parse_scale(arg: &str) -> Result<f64, String> {
+    match arg.parse::<f64>() {
+        Ok(scale) if scale > 0.0 => Ok(scale),
+        _ => Err(format!(
+            "Invalid scale: {} (expected a number greater than 0)",
+            arg
+        )),
+    }
+}
+
+/// Parses block coordinates given as x,z.
+fn parse_block_position(arg: &str) -> Result<(i32, i32), String> {
+    let parts: Vec<&str> = arg.split(',').map(|part: &str| part.trim()).collect();
+    match parts[..] {
+        [x, z] => match (x.parse::<i32>(), z.parse::<i32>()) {
+            (Ok(x), Ok(z)) => Ok((x, z)),
+            _ => Err(format!("Invalid block coordinates: {}", arg)),
+        },
+        _ => Err(format!("Expected block coordinates as x,z: {}", arg)),
+    }
+}
+
+/// Parses a geographic coordinate given as lat,lng.
+fn parse_lat_lng(arg: &str) -> Result<(f64, f64), String> {
+    let parts: Vec<&str> = arg.split(',').map(|part: &str| part.trim()).collect();
+    match parts[..] {
+        [lat, lng] => match (lat.parse::<f64>(), lng.parse::<f64>()) {
+            (Ok(lat), Ok(lng))
+                if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng) =>
+            {
+                Ok((lat, lng))
+            }
+            _ => Err(format!("Invalid coordinate: {}", arg)),
+        },
+        _ => Err(format!("Expected a coordinate as lat,lng: {}", arg)),
+    }
+}
+
 fn parse_duration(arg: &str) -> Result<std::time::Duration, std::num::ParseIntError> {
     let seconds = arg.parse()?;
     Ok(std::time::Duration::from_secs(seconds))
 }
+
+/// Arguments of `arnis restore`, which undoes a generation.
+#[derive(Parser, Debug)]
+#[command(
+    name = "arnis restore",
+    about = "Restore a world from a backup made before a generation"
+)]
+pub struct RestoreArgs {
+    /// Path to the Minecraft world
+    pub world: String,
+
+    /// Id of the backup, as printed when it was made
+    pub backup_id: String,
+}
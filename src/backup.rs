@@ -0,0 +1,166 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Folder inside the world holding one backup folder per generation.
+const BACKUP_DIR: &str = "arnis_backups";
+/// Files and folders copied into the backup, put back on restore.
+const COPIED_FILES: &str = "copied_files.txt";
+/// Files and folders that did not exist before the generation, removed again on restore.
+const CREATED_FILES: &str = "created_files.txt";
+
+/// Copies the given files and folders of the world, relative to the world folder, into a
+/// new backup folder named after the current time, and returns the id of the backup.
+pub fn create(world_dir: &Path, paths: &[PathBuf]) -> Result<String, String> {
+    let timestamp: u64 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let mut backup_id: String = timestamp.to_string();
+    let mut suffix: u32 = 1;
+    while backup_path(world_dir, &backup_id).exists() {
+        suffix += 1;
+        backup_id = format!("{}-{}", timestamp, suffix);
+    }
+
+    let backup_dir: PathBuf = backup_path(world_dir, &backup_id);
+    fs::create_dir_all(&backup_dir)
+        .map_err(|e| format!("Failed to create backup folder: {}", e))?;
+
+    let mut copied_files: Vec<String> = vec![];
+    let mut created_files: Vec<String> = vec![];
+    for path in paths.iter().filter(|path: &&PathBuf| is_relative(path)) {
+        let source: PathBuf = world_dir.join(path);
+        let name: String = path.to_string_lossy().replace('\\', "/");
+        if source.exists() {
+            copy(&source, &backup_dir.join(path))?;
+            copied_files.push(name);
+        } else {
+            created_files.push(name);
+        }
+    }
+    fs::write(backup_dir.join(COPIED_FILES), copied_files.join("\n"))
+        .and_then(|_| fs::write(backup_dir.join(CREATED_FILES), created_files.join("\n")))
+        .map_err(|e| format!("Failed to write backup: {}", e))?;
+
+    Ok(backup_id)
+}
+
+/// Puts the files and folders of the backup back into the world and removes the ones
+/// the generation created.
+pub fn restore(world_dir: &Path, backup_id: &str) -> Result<(), String> {
+    let backup_dir: PathBuf = backup_path(world_dir, backup_id);
+    let is_valid_id: bool = backup_id
+        .chars()
+        .all(|c: char| c.is_ascii_digit() || c == '-');
+    if !is_valid_id || !backup_dir.is_dir() {
+        let available: Vec<String> = list(world_dir);
+        return Err(if available.is_empty() {
+            format!("No backups found in {}", world_dir.display())
+        } else {
+            format!(
+                "No backup {} found (available: {})",
+                backup_id,
+                available.join(", ")
+            )
+        });
+    }
+
+    for file_name in read_list(&backup_dir.join(CREATED_FILES))? {
+        remove_path(&world_dir.join(file_name))?;
+    }
+
+    // Folders are replaced as a whole so files the generation added to them are dropped
+    for file_name in read_list(&backup_dir.join(COPIED_FILES))? {
+        let target: PathBuf = world_dir.join(&file_name);
+        if target.is_dir() {
+            remove_path(&target)?;
+        }
+        copy(&backup_dir.join(&file_name), &target)?;
+    }
+
+    Ok(())
+}
+
+/// Removes a backup that is no longer needed.
+pub fn remove(world_dir: &Path, backup_id: &str) -> Result<(), String> {
+    fs::remove_dir_all(backup_path(world_dir, backup_id))
+        .map_err(|e| format!("Failed to remove backup {}: {}", backup_id, e))
+}
+
+/// Ids of the backups of the world, oldest first.
+pub fn list(world_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(world_dir.join(BACKUP_DIR)) else {
+        return vec![];
+    };
+
+    let mut backup_ids: Vec<String> = entries
+        .flatten()
+        .filter(|entry: &fs::DirEntry| entry.path().is_dir())
+        .map(|entry: fs::DirEntry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    backup_ids.sort_by_key(|backup_id: &String| {
+        let (timestamp, suffix) = backup_id.split_once('-').unwrap_or((backup_id, "1"));
+        (
+            timestamp.parse::<u64>().unwrap_or(0),
+            suffix.parse::<u32>().unwrap_or(0),
+        )
+    });
+
+    backup_ids
+}
+
+fn backup_path(world_dir: &Path, backup_id: &str) -> PathBuf {
+    world_dir.join(BACKUP_DIR).join(backup_id)
+}
+
+/// Whether the path stays inside the folder it is relative to.
+fn is_relative(path: &Path) -> bool {
+    path.components()
+        .all(|component: Component| matches!(component, Component::Normal(_)))
+}
+
+/// Paths listed in a file of the backup, one per line.
+fn read_list(list_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let list: String =
+        fs::read_to_string(list_path).map_err(|e| format!("Failed to read backup: {}", e))?;
+    Ok(list
+        .lines()
+        .map(PathBuf::from)
+        .filter(|path: &PathBuf| !path.as_os_str().is_empty() && is_relative(path))
+        .collect())
+}
+
+/// Copies a file, or a folder with everything in it, creating the parent folders.
+fn copy(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    if from.is_dir() {
+        let entries: fs::ReadDir =
+            fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+        fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+        for entry in entries.flatten() {
+            copy(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        return Ok(());
+    }
+
+    fs::copy(from, to)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to copy {}: {}", from.display(), e))
+}
+
+/// Removes a file or a folder with everything in it, if it exists.
+fn remove_path(path: &Path) -> Result<(), String> {
+    let result: std::io::Result<()> = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else if path.exists() {
+        fs::remove_file(path)
+    } else {
+        Ok(())
+    };
+    result.map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+#[derive(Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Debug)]
+pub enum Biome {
+    Beach,
+    Forest,
+    FrozenOcean,
+    FrozenRiver,
+    Ocean,
+    Plains,
+    River,
+    SnowyBeach,
+    SnowyPlains,
+    SnowyTaiga,
+    Swamp,
+}
+
+impl Biome {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Beach => "minecraft:beach",
+            Self::Forest => "minecraft:forest",
+            Self::FrozenOcean => "minecraft:frozen_ocean",
+            Self::FrozenRiver => "minecraft:frozen_river",
+            Self::Ocean => "minecraft:ocean",
+            Self::Plains => "minecraft:plains",
+            Self::River => "minecraft:river",
+            Self::SnowyBeach => "minecraft:snowy_beach",
+            Self::SnowyPlains => "minecraft:snowy_plains",
+            Self::SnowyTaiga => "minecraft:snowy_taiga",
+            Self::Swamp => "minecraft:swamp",
+        }
+    }
+
+    /// Snowy counterpart of the biome, used in winter mode so it snows instead of rains.
+    pub fn winter_variant(&self) -> Biome {
+        match self {
+            Self::Beach => Self::SnowyBeach,
+            Self::Forest => Self::SnowyTaiga,
+            Self::Ocean => Self::FrozenOcean,
+            Self::River => Self::FrozenRiver,
+            Self::Plains | Self::Swamp => Self::SnowyPlains,
+            _ => *self,
+        }
+    }
+}
+
+/// Picks the biome of a water body based on its `water` tag.
+pub fn water_biome(tags: &HashMap<String, String>) -> Biome {
+    match tags.get("water").map(|water: &String| water.as_str()) {
+        Some("sea" | "ocean" | "bay" | "lagoon" | "strait") => Biome::Ocean,
+        _ => Biome::River,
+    }
+}
@@ -3,9 +3,13 @@
 use fastnbt::Value;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::RwLock;
 
 use crate::colors::RGBTuple;
+use crate::minecraft_version::{MinecraftVersion, V1_19, V1_20, V1_20_3};
 
 #[allow(clippy::enum_variant_names)]
 #[derive(Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Debug)]
@@ -14,6 +18,7 @@ pub enum Block {
     AcaciaPlanks,
     Air,
     Andesite,
+    Barrel,
     BirchLeaves,
     BirchLog,
     BlackConcrete,
@@ -22,6 +27,7 @@ pub enum Block {
     BlueTerracotta,
     Brick,
     Cauldron,
+    Chest,
     ChiseledStoneBricks,
     CobblestoneWall,
     Cobblestone,
@@ -31,6 +37,7 @@ pub enum Block {
     CutSandstone,
     CyanConcrete,
     DarkOakPlanks,
+    Deepslate,
     DeepslateBricks,
     Diorite,
     Dirt,
@@ -51,6 +58,7 @@ pub enum Block {
     IronBlock,
     JunglePlanks,
     Ladder,
+    Lectern,
     LightBlueConcrete,
     LightBlueTerracotta,
     LightGrayConcrete,
@@ -63,6 +71,7 @@ pub enum Block {
     OakLog,
     OakPlanks,
     OakSlab,
+    OakStairs,
     OrangeTerracotta,
     Podzol,
     PolishedAndesite,
@@ -93,10 +102,12 @@ pub enum Block {
     SprucePlanks,
     StoneBlockSlab,
     StoneBrickSlab,
+    StoneBrickStairs,
     StoneBricks,
     Stone,
     Terracotta,
     WarpedPlanks,
+    WhiteBanner,
     Water,
     WhiteConcrete,
     WhiteFlower,
@@ -118,6 +129,9 @@ pub enum Block {
     SnowBlock,
     SnowLayer,
     Sign,
+    OakWallSign,
+    OakHangingSign,
+    OakWallHangingSign,
 
     Carrots,
     DarkOakDoorLower,
@@ -126,18 +140,186 @@ pub enum Block {
     Wheat,
 
     Bedrock,
+
+    /// Any block not listed above, such as a block added by a mod
+    Custom(CustomBlockId),
 }
 
+/// Every block, to look blocks up by name.
+const ALL_BLOCKS: [Block; 123] = [
+    Block::AcaciaPlanks,
+    Block::Air,
+    Block::Andesite,
+    Block::Barrel,
+    Block::BirchLeaves,
+    Block::BirchLog,
+    Block::BlackConcrete,
+    Block::Blackstone,
+    Block::BlueFlower,
+    Block::BlueTerracotta,
+    Block::Brick,
+    Block::Cauldron,
+    Block::Chest,
+    Block::ChiseledStoneBricks,
+    Block::CobblestoneWall,
+    Block::Cobblestone,
+    Block::CrackedPolishedBlackstoneBricks,
+    Block::CrackedStoneBricks,
+    Block::CrimsonPlanks,
+    Block::CutSandstone,
+    Block::CyanConcrete,
+    Block::DarkOakPlanks,
+    Block::Deepslate,
+    Block::DeepslateBricks,
+    Block::Diorite,
+    Block::Dirt,
+    Block::EndStoneBricks,
+    Block::Farmland,
+    Block::Glass,
+    Block::Glowstone,
+    Block::Granite,
+    Block::GrassBlock,
+    Block::Grass,
+    Block::Gravel,
+    Block::GrayConcrete,
+    Block::GrayTerracotta,
+    Block::GreenStainedHardenedClay,
+    Block::GreenWool,
+    Block::HayBale,
+    Block::IronBars,
+    Block::IronBlock,
+    Block::JunglePlanks,
+    Block::Ladder,
+    Block::Lectern,
+    Block::LightBlueConcrete,
+    Block::LightBlueTerracotta,
+    Block::LightGrayConcrete,
+    Block::MossBlock,
+    Block::MossyCobblestone,
+    Block::MudBricks,
+    Block::NetherBricks,
+    Block::OakFence,
+    Block::OakLeaves,
+    Block::OakLog,
+    Block::OakPlanks,
+    Block::OakSlab,
+    Block::OakStairs,
+    Block::OrangeTerracotta,
+    Block::Podzol,
+    Block::PolishedAndesite,
+    Block::PolishedBasalt,
+    Block::PolishedBlackstoneBricks,
+    Block::PolishedBlackstone,
+    Block::PolishedDeepslate,
+    Block::PolishedDiorite,
+    Block::PolishedGranite,
+    Block::Prismarine,
+    Block::PurpurBlock,
+    Block::PurpurPillar,
+    Block::QuartzBricks,
+    Block::Rail,
+    Block::RedFlower,
+    Block::RedNetherBricks,
+    Block::RedTerracotta,
+    Block::RedWool,
+    Block::Sand,
+    Block::Sandstone,
+    Block::Scaffolding,
+    Block::SmoothQuartz,
+    Block::SmoothRedSandstone,
+    Block::SmoothSandstone,
+    Block::SmoothStone,
+    Block::Sponge,
+    Block::SpruceLog,
+    Block::SprucePlanks,
+    Block::StoneBlockSlab,
+    Block::StoneBrickSlab,
+    Block::StoneBrickStairs,
+    Block::StoneBricks,
+    Block::Stone,
+    Block::Terracotta,
+    Block::WarpedPlanks,
+    Block::WhiteBanner,
+    Block::Water,
+    Block::WhiteConcrete,
+    Block::WhiteFlower,
+    Block::WhiteStainedGlass,
+    Block::WhiteTerracotta,
+    Block::WhiteWool,
+    Block::YellowConcrete,
+    Block::YellowFlower,
+    Block::YellowWool,
+    Block::LimeConcrete,
+    Block::CyanWool,
+    Block::BlueConcrete,
+    Block::PurpleConcrete,
+    Block::RedConcrete,
+    Block::MagentaConcrete,
+    Block::BrownWool,
+    Block::OxidizedCopper,
+    Block::YellowTerracotta,
+    Block::SnowBlock,
+    Block::SnowLayer,
+    Block::Sign,
+    Block::OakWallSign,
+    Block::OakHangingSign,
+    Block::OakWallHangingSign,
+    Block::Carrots,
+    Block::DarkOakDoorLower,
+    Block::DarkOakDoorUpper,
+    Block::Potatoes,
+    Block::Wheat,
+    Block::Bedrock,
+];
+
 impl Block {
     pub fn namespace(&self) -> &str {
-        "mincraft"
+        match self {
+            Self::Custom(id) => &id.get().namespace,
+            _ => "minecraft",
+        }
+    }
+
+    /// The block with the given name, with or without the `minecraft:` namespace.
+    /// Names with another namespace or with properties, like `create:andesite_casing`
+    /// or `oak_log[axis=x]`, give a custom block.
+    pub fn from_name(name: &str) -> Option<Block> {
+        let custom: CustomBlock = CustomBlock::parse(name).ok()?;
+        // Without a namespace only built-in blocks are known, which catches typos
+        if !name.contains(':') && Self::builtin(&custom.name).is_none() {
+            return None;
+        }
+        Some(custom.into_block())
+    }
+
+    /// A block given by its full id in the form `namespace:name[key=value,...]`,
+    /// built-in blocks without properties give their usual variant.
+    pub fn custom(id: &str) -> Result<Block, String> {
+        if !id.contains(':') {
+            return Err(format!("Block id without namespace: {}", id));
+        }
+        Ok(CustomBlock::parse(id)?.into_block())
+    }
+
+    fn builtin(name: &str) -> Option<Block> {
+        ALL_BLOCKS
+            .iter()
+            .find(|block: &&Block| block.name() == name)
+            .copied()
+    }
+
+    /// Full id of the block in the given version, e.g. `minecraft:oak_log`.
+    pub fn id_for(&self, version: MinecraftVersion) -> String {
+        format!("{}:{}", self.namespace(), self.name_for(version))
     }
 
     pub fn name(&self) -> &str {
         match self {
+            Self::Custom(id) => &id.get().name,
             Self::AcaciaPlanks => "acacia_planks",
             Self::Air => "air",
             Self::Andesite => "andesite",
+            Self::Barrel => "barrel",
             Self::BirchLeaves => "birch_leaves",
             Self::BirchLog => "birch_log",
             Self::BlackConcrete => "black_concrete",
@@ -146,6 +328,7 @@ impl Block {
             Self::BlueTerracotta => "blue_terracotta",
             Self::Brick => "bricks",
             Self::Cauldron => "cauldron",
+            Self::Chest => "chest",
             Self::ChiseledStoneBricks => "chiseled_stone_bricks",
             Self::CobblestoneWall => "cobblestone_wall",
             Self::Cobblestone => "cobblestone",
@@ -155,6 +338,7 @@ impl Block {
             Self::CutSandstone => "cut_sandstone",
             Self::CyanConcrete => "cyan_concrete",
             Self::DarkOakPlanks => "dark_oak_planks",
+            Self::Deepslate => "deepslate",
             Self::DeepslateBricks => "deepslate_bricks",
             Self::Diorite => "diorite",
             Self::Dirt => "dirt",
@@ -164,7 +348,7 @@ impl Block {
             Self::Glowstone => "glowstone",
             Self::Granite => "granite",
             Self::GrassBlock => "grass_block",
-            Self::Grass => "tall_grass",
+            Self::Grass => "short_grass",
             Self::Gravel => "gravel",
             Self::GrayConcrete => "gray_concrete",
             Self::GrayTerracotta => "gray_terracotta",
@@ -175,6 +359,7 @@ impl Block {
             Self::IronBlock => "iron_block",
             Self::JunglePlanks => "jungle_planks",
             Self::Ladder => "ladder",
+            Self::Lectern => "lectern",
             Self::LightBlueConcrete => "light_blue_concrete",
             Self::LightBlueTerracotta => "light_blue_terracotta",
             Self::LightGrayConcrete => "light_gray_concrete",
@@ -187,6 +372,7 @@ impl Block {
             Self::OakLog => "oak_log",
             Self::OakPlanks => "oak_planks",
             Self::OakSlab => "oak_slab",
+            Self::OakStairs => "oak_stairs",
             Self::OrangeTerracotta => "orange_terracotta",
             Self::Podzol => "podzol",
             Self::PolishedAndesite => "polished_andesite",
@@ -217,10 +403,12 @@ impl Block {
             Self::SprucePlanks => "spruce_planks",
             Self::StoneBlockSlab => "stone_slab",
             Self::StoneBrickSlab => "stone_brick_slab",
+            Self::StoneBrickStairs => "stone_brick_stairs",
             Self::StoneBricks => "stone_bricks",
             Self::Stone => "stone",
             Self::Terracotta => "terracotta",
             Self::WarpedPlanks => "warped_planks",
+            Self::WhiteBanner => "white_banner",
             Self::Water => "water",
             Self::WhiteConcrete => "white_concrete",
             Self::WhiteFlower => "azure_bluet",
@@ -248,6 +436,9 @@ impl Block {
             Self::SnowBlock => "snow_block",
             Self::SnowLayer => "snow",
             Self::Sign => "oak_sign",
+            Self::OakWallSign => "oak_wall_sign",
+            Self::OakHangingSign => "oak_hanging_sign",
+            Self::OakWallHangingSign => "oak_wall_hanging_sign",
         }
     }
 
@@ -283,6 +474,12 @@ impl Block {
                 map
             })),
 
+            Self::Lectern => Some(Value::Compound({
+                let mut map: HashMap<String, Value> = HashMap::new();
+                map.insert("has_book".to_string(), Value::String("true".to_string()));
+                map
+            })),
+
             Self::Sign => Some(Value::Compound({
                 let mut map: HashMap<String, Value> = HashMap::new();
                 map.insert("rotation".to_string(), Value::String("6".to_string()));
@@ -293,9 +490,387 @@ impl Block {
                 map
             })),
 
+            Self::Custom(id) if !id.get().properties.is_empty() => Some(Value::Compound(
+                id.get()
+                    .properties
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+                    .collect(),
+            )),
+
             _ => None,
         }
     }
+
+    /// Light level emitted by the block (0-15).
+    pub fn light_emission(&self) -> u8 {
+        match self {
+            Self::Glowstone => 15,
+            _ => 0,
+        }
+    }
+
+    /// How much light is absorbed when passing through the block (0-15).
+    /// Full blocks absorb all light, foliage and water only dim it. Custom blocks count
+    /// as full blocks, chunks holding them are lit by Minecraft.
+    pub fn light_opacity(&self) -> u8 {
+        match self {
+            Self::Air
+            | Self::Glass
+            | Self::WhiteStainedGlass
+            | Self::IronBars
+            | Self::OakFence
+            | Self::CobblestoneWall
+            | Self::Rail
+            | Self::Ladder
+            | Self::Scaffolding
+            | Self::Cauldron
+            | Self::Chest
+            | Self::Lectern
+            | Self::Sign
+            | Self::OakWallSign
+            | Self::OakHangingSign
+            | Self::OakWallHangingSign
+            | Self::WhiteBanner
+            | Self::DarkOakDoorLower
+            | Self::DarkOakDoorUpper
+            | Self::SnowLayer
+            | Self::Grass
+            | Self::RedFlower
+            | Self::YellowFlower
+            | Self::BlueFlower
+            | Self::WhiteFlower
+            | Self::Carrots
+            | Self::Potatoes
+            | Self::Wheat => 0,
+
+            Self::OakLeaves | Self::BirchLeaves | Self::Water => 1,
+
+            _ => 15,
+        }
+    }
+
+    /// Whether entities collide with the block, used for the motion blocking heightmaps.
+    pub fn blocks_motion(&self) -> bool {
+        !matches!(
+            self,
+            Self::Air
+                | Self::Water
+                | Self::Rail
+                | Self::Ladder
+                | Self::Scaffolding
+                | Self::Sign
+                | Self::OakWallSign
+                | Self::OakHangingSign
+                | Self::OakWallHangingSign
+                | Self::WhiteBanner
+                | Self::SnowLayer
+                | Self::Grass
+                | Self::RedFlower
+                | Self::YellowFlower
+                | Self::BlueFlower
+                | Self::WhiteFlower
+                | Self::Carrots
+                | Self::Potatoes
+                | Self::Wheat
+        )
+    }
+
+    /// Whether the block is a full cube that fences, walls and panes attach to.
+    pub fn is_solid(&self) -> bool {
+        self.blocks_motion()
+            && self.light_opacity() == 15
+            && !matches!(
+                self,
+                Self::OakSlab
+                    | Self::StoneBlockSlab
+                    | Self::StoneBrickSlab
+                    | Self::OakStairs
+                    | Self::StoneBrickStairs
+            )
+    }
+
+    pub fn is_liquid(&self) -> bool {
+        matches!(self, Self::Water)
+    }
+
+    pub fn is_leaves(&self) -> bool {
+        matches!(self, Self::OakLeaves | Self::BirchLeaves)
+    }
+
+    /// Color of the block on maps seen from above, `None` for blocks maps look through.
+    pub fn map_color(&self) -> Option<RGBTuple> {
+        let color: RGBTuple = match self {
+            Self::Air
+            | Self::Glass
+            | Self::IronBars
+            | Self::Rail
+            | Self::Ladder
+            | Self::DarkOakDoorLower
+            | Self::DarkOakDoorUpper => return None,
+
+            Self::GrassBlock | Self::Grass => (127, 178, 56),
+            Self::Sand
+            | Self::Sandstone
+            | Self::CutSandstone
+            | Self::SmoothSandstone
+            | Self::Scaffolding => (247, 233, 163),
+            Self::WhiteWool | Self::Cauldron => (199, 199, 199),
+            Self::IronBlock | Self::Lectern => (167, 167, 167),
+            Self::OakLeaves
+            | Self::BirchLeaves
+            | Self::Carrots
+            | Self::Potatoes
+            | Self::Wheat
+            | Self::RedFlower
+            | Self::YellowFlower
+            | Self::BlueFlower
+            | Self::WhiteFlower => (0, 124, 0),
+            Self::SnowBlock
+            | Self::SnowLayer
+            | Self::WhiteConcrete
+            | Self::WhiteStainedGlass
+            | Self::WhiteBanner => (255, 255, 255),
+            Self::Dirt | Self::Farmland | Self::JunglePlanks | Self::Granite => (151, 109, 77),
+            Self::Stone
+            | Self::Andesite
+            | Self::PolishedAndesite
+            | Self::Cobblestone
+            | Self::CobblestoneWall
+            | Self::MossyCobblestone
+            | Self::Gravel
+            | Self::SmoothStone
+            | Self::StoneBricks
+            | Self::CrackedStoneBricks
+            | Self::ChiseledStoneBricks
+            | Self::StoneBlockSlab
+            | Self::StoneBrickSlab
+            | Self::StoneBrickStairs
+            | Self::Bedrock => (112, 112, 112),
+            Self::Water => (64, 64, 255),
+            Self::OakPlanks
+            | Self::OakLog
+            | Self::OakSlab
+            | Self::OakStairs
+            | Self::OakFence
+            | Self::Chest
+            | Self::Barrel
+            | Self::Sign
+            | Self::OakWallSign
+            | Self::OakHangingSign
+            | Self::OakWallHangingSign => (143, 119, 72),
+            Self::Diorite
+            | Self::PolishedDiorite
+            | Self::QuartzBricks
+            | Self::SmoothQuartz
+            | Self::BirchLog => (255, 252, 245),
+            Self::AcaciaPlanks | Self::SmoothRedSandstone => (216, 127, 51),
+            Self::MagentaConcrete | Self::PurpurBlock | Self::PurpurPillar => (178, 76, 216),
+            Self::LightBlueConcrete => (102, 153, 216),
+            Self::YellowConcrete
+            | Self::YellowWool
+            | Self::HayBale
+            | Self::Sponge
+            | Self::Glowstone => (229, 229, 51),
+            Self::LimeConcrete => (127, 204, 25),
+            Self::GrayConcrete => (76, 76, 76),
+            Self::LightGrayConcrete => (153, 153, 153),
+            Self::CyanConcrete | Self::CyanWool | Self::Prismarine => (76, 127, 153),
+            Self::PurpleConcrete => (127, 63, 178),
+            Self::BlueConcrete => (51, 76, 178),
+            Self::DarkOakPlanks | Self::BrownWool => (102, 76, 51),
+            Self::MossBlock | Self::GreenWool => (102, 127, 51),
+            Self::RedConcrete | Self::RedWool | Self::Brick => (153, 51, 51),
+            Self::BlackConcrete
+            | Self::Blackstone
+            | Self::PolishedBlackstone
+            | Self::PolishedBlackstoneBricks
+            | Self::CrackedPolishedBlackstoneBricks
+            | Self::PolishedBasalt => (25, 25, 25),
+            Self::Podzol | Self::SpruceLog | Self::SprucePlanks => (129, 86, 49),
+            Self::NetherBricks | Self::RedNetherBricks => (112, 2, 0),
+            Self::WhiteTerracotta | Self::PolishedGranite => (209, 177, 161),
+            Self::OrangeTerracotta | Self::Terracotta => (159, 82, 36),
+            Self::LightBlueTerracotta => (112, 108, 138),
+            Self::YellowTerracotta | Self::EndStoneBricks => (186, 133, 36),
+            Self::GrayTerracotta => (57, 41, 35),
+            Self::BlueTerracotta => (76, 62, 92),
+            Self::RedTerracotta => (142, 60, 46),
+            Self::GreenStainedHardenedClay => (76, 82, 42),
+            Self::MudBricks => (135, 107, 98),
+            Self::CrimsonPlanks => (148, 63, 97),
+            Self::WarpedPlanks => (58, 142, 140),
+            Self::OxidizedCopper => (22, 126, 134),
+            Self::Deepslate | Self::DeepslateBricks | Self::PolishedDeepslate => (100, 100, 100),
+            // Colors of custom blocks are unknown, show them like stone
+            Self::Custom(_) => (112, 112, 112),
+        };
+
+        Some(color)
+    }
+
+    /// Name of the block in the given version, for blocks that were renamed.
+    pub fn name_for(&self, version: MinecraftVersion) -> &str {
+        match self {
+            Self::Grass if !version.at_least(V1_20_3) => "grass",
+            _ => self.name(),
+        }
+    }
+
+    /// Data version of the release that added the block.
+    fn introduced_in(&self) -> i32 {
+        match self {
+            Self::MudBricks => V1_19,
+            Self::OakHangingSign | Self::OakWallHangingSign => V1_20,
+            _ => 0,
+        }
+    }
+
+    /// The block itself if it exists in the given version, otherwise the closest
+    /// block that does.
+    pub fn for_version(&self, version: MinecraftVersion) -> Block {
+        if version.at_least(self.introduced_in()) {
+            return *self;
+        }
+
+        match self {
+            Self::MudBricks => Self::Terracotta,
+            Self::OakHangingSign => Self::Sign,
+            Self::OakWallHangingSign => Self::OakWallSign,
+            _ => *self,
+        }
+    }
+}
+
+/// Handle of an interned custom block, small and cheap to copy like the built-in blocks.
+/// Interned blocks are never freed, so the handle points at the block itself and reading
+/// it takes no lock.
+#[derive(Copy, Clone, Debug)]
+pub struct CustomBlockId(&'static CustomBlock);
+
+impl CustomBlockId {
+    fn get(self) -> &'static CustomBlock {
+        self.0
+    }
+}
+
+// Interning keeps one copy of each block, so handles are equal exactly when they point
+// at the same block. Ordering by content keeps sorted output the same between runs.
+impl PartialEq for CustomBlockId {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for CustomBlockId {}
+
+impl Hash for CustomBlockId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self.0, state);
+    }
+}
+
+impl PartialOrd for CustomBlockId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CustomBlockId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(other.0)
+    }
+}
+
+/// A block outside the built-in ones, given by its namespaced name and properties.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+struct CustomBlock {
+    namespace: String,
+    name: String,
+    properties: BTreeMap<String, String>,
+}
+
+/// Custom blocks used so far, looked up when a block id is parsed. They are never freed,
+/// so handles stay valid and names can be borrowed for the rest of the run.
+#[derive(Default)]
+struct CustomBlocks {
+    ids: HashMap<&'static CustomBlock, CustomBlockId>,
+}
+
+static CUSTOM_BLOCKS: Lazy<RwLock<CustomBlocks>> = Lazy::new(RwLock::default);
+
+impl CustomBlock {
+    /// Parses `namespace:name[key=value,...]`, where the namespace defaults to `minecraft`.
+    fn parse(id: &str) -> Result<Self, String> {
+        let id: &str = id.trim();
+        let (name, properties): (&str, &str) = match id.split_once('[') {
+            Some((name, rest)) => match rest.strip_suffix(']') {
+                Some(properties) => (name, properties),
+                None => return Err(format!("Missing ] in block id: {}", id)),
+            },
+            None => (id, ""),
+        };
+        let (namespace, name): (&str, &str) = name.split_once(':').unwrap_or(("minecraft", name));
+
+        let is_valid = |part: &str, extra: &str| {
+            !part.is_empty()
+                && part.chars().all(|c: char| {
+                    c.is_ascii_lowercase()
+                        || c.is_ascii_digit()
+                        || "_-.".contains(c)
+                        || extra.contains(c)
+                })
+        };
+        if !is_valid(namespace, "") || !is_valid(name, "/") {
+            return Err(format!("Invalid block id: {}", id));
+        }
+
+        let mut parsed: BTreeMap<String, String> = BTreeMap::new();
+        for property in properties
+            .split(',')
+            .filter(|property: &&str| !property.trim().is_empty())
+        {
+            match property.split_once('=') {
+                Some((key, value)) if is_valid(key.trim(), "") && is_valid(value.trim(), "") => {
+                    parsed.insert(key.trim().to_string(), value.trim().to_string());
+                }
+                _ => return Err(format!("Invalid property {} in block id: {}", property, id)),
+            }
+        }
+
+        Ok(Self {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            properties: parsed,
+        })
+    }
+
+    /// The built-in block if this is one, otherwise the interned custom block.
+    fn into_block(self) -> Block {
+        if self.namespace == "minecraft" && self.properties.is_empty() {
+            if let Some(block) = Block::builtin(&self.name) {
+                return block;
+            }
+        }
+        self.intern()
+    }
+
+    /// The block for this id, reusing the id of an identical block seen before.
+    fn intern(self) -> Block {
+        if let Some(id) = CUSTOM_BLOCKS.read().unwrap().ids.get(&self) {
+            return Block::Custom(*id);
+        }
+
+        let mut custom_blocks = CUSTOM_BLOCKS.write().unwrap();
+        // Another thread may have added it in the meantime
+        if let Some(id) = custom_blocks.ids.get(&self) {
+            return Block::Custom(*id);
+        }
+        let block: &'static CustomBlock = Box::leak(Box::new(self));
+        let id: CustomBlockId = CustomBlockId(block);
+        custom_blocks.ids.insert(block, id);
+        Block::Custom(id)
+    }
 }
 
 // Variations for building corners
@@ -3,9 +3,32 @@ use crate::block_definitions::Block;
 use crate::element_processing::*;
 use crate::osm_parser::ProcessedElement;
 use crate::progress::emit_gui_progress_update;
-use crate::world_editor::WorldEditor;
+use crate::session_lock::SessionLock;
+use crate::style::Style;
+use crate::world_editor::datapack::{self, Waypoint};
+use crate::world_editor::{ground_profile, WorldEditor, INDEX_FILE};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Tags that decide how an element is generated, used to group elements in the dry run summary.
+const SUMMARY_CATEGORIES: [&str; 13] = [
+    "building", "highway", "landuse", "natural", "amenity", "leisure", "barrier", "waterway",
+    "railway", "aeroway", "door", "tourism", "water",
+];
+
+/// Tags of the named features that get a waypoint, any value if none is given.
+const WAYPOINT_TAGS: [(&str, Option<&str>); 8] = [
+    ("place", None),
+    ("railway", Some("station")),
+    ("public_transport", Some("station")),
+    ("leisure", Some("park")),
+    ("tourism", Some("attraction")),
+    ("tourism", Some("museum")),
+    ("amenity", Some("townhall")),
+    ("aeroway", Some("aerodrome")),
+];
 
 pub fn generate_world(
     elements: Vec<ProcessedElement>,
@@ -16,10 +39,18 @@ pub fn generate_world(
     println!("{} Processing data...", "[3/5]".bold());
     emit_gui_progress_update(10.0, "Processing data...");
 
+    // Hold the world lock until everything is written, so the world cannot be opened meanwhile
+    let _session_lock: Option<SessionLock> = if args.dry_run || args.bedrock {
+        None
+    } else {
+        Some(SessionLock::acquire(Path::new(&args.path))?)
+    };
+
     let ground_level: i32 = args.ground_level;
     let region_dir: String = format!("{}/region", args.path);
+    let style: Style = Style::load(args)?;
     let mut editor: WorldEditor =
-        WorldEditor::new(&region_dir, scale_factor_x, scale_factor_z, args);
+        WorldEditor::new(&region_dir, scale_factor_x, scale_factor_z, args, style);
 
     editor.set_sign(
         "↑".to_string(),
@@ -61,50 +92,87 @@ pub fn generate_world(
             process_pb.set_message("");
         }
 
-        match element {
+        editor.footprints_mut().begin();
+
+        // Key of the tag that decided how the element is generated
+        let handled_by: Option<&str> = match element {
             ProcessedElement::Way(way) => {
                 if way.tags.contains_key("building") || way.tags.contains_key("building:part") {
                     buildings::generate_buildings(&mut editor, way, ground_level, args, None);
+                    Some(if way.tags.contains_key("building") {
+                        "building"
+                    } else {
+                        "building:part"
+                    })
                 } else if way.tags.contains_key("highway") {
                     highways::generate_highways(&mut editor, element, ground_level, args);
+                    Some("highway")
                 } else if way.tags.contains_key("landuse") {
                     landuse::generate_landuse(&mut editor, way, ground_level, args);
+                    Some("landuse")
                 } else if way.tags.contains_key("natural") {
                     natural::generate_natural(&mut editor, element, ground_level, args);
+                    Some("natural")
                 } else if way.tags.contains_key("amenity") {
                     amenities::generate_amenities(&mut editor, element, ground_level, args);
+                    Some("amenity")
                 } else if way.tags.contains_key("leisure") {
                     leisure::generate_leisure(&mut editor, way, ground_level, args);
+                    Some("leisure")
                 } else if way.tags.contains_key("barrier") {
                     barriers::generate_barriers(&mut editor, element, ground_level);
+                    Some("barrier")
                 } else if way.tags.contains_key("waterway") {
                     waterways::generate_waterways(&mut editor, way, ground_level);
+                    Some("waterway")
                 } else if way.tags.contains_key("bridge") {
                     //bridges::generate_bridges(&mut editor, way, ground_level); // TODO FIX
+                    None
                 } else if way.tags.contains_key("railway") {
                     railways::generate_railways(&mut editor, way, ground_level);
+                    Some("railway")
                 } else if way.tags.contains_key("aeroway") || way.tags.contains_key("area:aeroway")
                 {
                     highways::generate_aeroway(&mut editor, way, ground_level);
+                    Some(if way.tags.contains_key("aeroway") {
+                        "aeroway"
+                    } else {
+                        "area:aeroway"
+                    })
                 } else if way.tags.get("service") == Some(&"siding".to_string()) {
                     highways::generate_siding(&mut editor, way, ground_level);
+                    Some("service")
+                } else {
+                    None
                 }
             }
             ProcessedElement::Node(node) => {
                 if node.tags.contains_key("door") || node.tags.contains_key("entrance") {
                     doors::generate_doors(&mut editor, node, ground_level);
+                    Some(if node.tags.contains_key("door") {
+                        "door"
+                    } else {
+                        "entrance"
+                    })
                 } else if node.tags.contains_key("natural")
                     && node.tags.get("natural") == Some(&"tree".to_string())
                 {
                     natural::generate_natural(&mut editor, element, ground_level, args);
+                    Some("natural")
                 } else if node.tags.contains_key("amenity") {
                     amenities::generate_amenities(&mut editor, element, ground_level, args);
+                    Some("amenity")
                 } else if node.tags.contains_key("barrier") {
                     barriers::generate_barriers(&mut editor, element, ground_level);
+                    Some("barrier")
                 } else if node.tags.contains_key("highway") {
                     highways::generate_highways(&mut editor, element, ground_level, args);
+                    Some("highway")
                 } else if node.tags.contains_key("tourism") {
                     tourisms::generate_tourisms(&mut editor, node, ground_level);
+                    Some("tourism")
+                } else {
+                    None
                 }
             }
             ProcessedElement::Relation(rel) => {
@@ -115,15 +183,46 @@ pub fn generate_world(
                         ground_level,
                         args,
                     );
+                    Some(if rel.tags.contains_key("building") {
+                        "building"
+                    } else {
+                        "building:part"
+                    })
                 } else if rel.tags.contains_key("water") {
                     water_areas::generate_water_areas(&mut editor, rel, ground_level);
+                    Some("water")
+                } else {
+                    None
                 }
             }
+        };
+
+        match handled_by {
+            Some(key) => editor.report_mut().handled(element.tags(), key),
+            None => editor.report_mut().unhandled(element.tags()),
         }
+
+        // Index the blocks of the element by its OSM id, with the tags that identify it
+        let key_tags: BTreeMap<String, String> = element
+            .tags()
+            .iter()
+            .filter(|(key, _)| key.as_str() == "name" || Some(key.as_str()) == handled_by)
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        editor
+            .footprints_mut()
+            .finish(element.kind(), element.id(), key_tags);
     }
 
     process_pb.finish();
 
+    editor.report_mut().print();
+    if let Some(path) = &args.report_json {
+        if let Err(e) = editor.report_mut().write_json(path) {
+            eprintln!("{}", format!("Error! {}", e).red().bold());
+        }
+    }
+
     // Generate ground layer
     let total_blocks: u64 = (scale_factor_x as i32 + 1) as u64 * (scale_factor_z as i32 + 1) as u64;
     let desired_updates: u64 = 1500;
@@ -147,16 +246,14 @@ pub fn generate_world(
     let total_iterations_grnd: f64 = (scale_factor_x + 1.0) * (scale_factor_z + 1.0);
     let progress_increment_grnd: f64 = 30.0 / total_iterations_grnd;
 
-    let groundlayer_block = if args.winter {
-        Block::SnowBlock
-    } else {
-        Block::GrassBlock
-    };
+    // Only fills the space elements left empty, so water and other elements below the surface stay
+    let profile: Vec<(Block, i32, i32)> = ground_profile(args);
 
     for x in 0..=(scale_factor_x as i32) {
         for z in 0..=(scale_factor_z as i32) {
-            editor.set_block(groundlayer_block, x, ground_level, z, None, None);
-            editor.set_block(Block::Dirt, x, ground_level - 1, z, None, None);
+            for &(block, min_y, max_y) in &profile {
+                editor.fill_blocks(block, x, min_y, z, x, max_y, z, None, None);
+            }
 
             block_counter += 1;
             if block_counter % batch_size == 0 {
@@ -174,10 +271,96 @@ pub fn generate_world(
     ground_pb.inc(block_counter % batch_size);
     ground_pb.finish();
 
-    // Save world
-    editor.save();
+    if args.dry_run {
+        println!("{} Summary (dry run, nothing was written):", "[5/5]".bold());
+        print_element_summary(&elements);
+        editor.print_summary();
+        emit_gui_progress_update(100.0, "Done! Dry run completed.");
+        return Ok(());
+    }
+
+    // Save world, writing nothing next to it if that failed
+    editor.save()?;
+
+    // Where each element ended up, next to the world data
+    let index_path: PathBuf = Path::new(&args.path).join(INDEX_FILE);
+    match editor.footprints().write(&index_path) {
+        Ok(()) => println!(
+            "Wrote the block footprints of {} elements to {}",
+            editor.footprints().len(),
+            index_path.display()
+        ),
+        Err(e) => eprintln!("{}", format!("Error! {}", e).red().bold()),
+    }
+
+    // Teleport functions for the named places of the area
+    if !args.bedrock {
+        let waypoints: Vec<Waypoint> = collect_waypoints(&elements, &editor);
+        match datapack::write_waypoints(Path::new(&args.path), &waypoints, args.mc_version) {
+            Ok(()) if !waypoints.is_empty() => println!(
+                "Added {} waypoints, run /function arnis:waypoints in game to list them",
+                waypoints.len()
+            ),
+            Ok(()) => {}
+            Err(e) => eprintln!("{}", format!("Error! {}", e).red().bold()),
+        }
+    }
 
     emit_gui_progress_update(100.0, "Done! World generation completed.");
     println!("{}", "Done! World generation completed.".green().bold());
     Ok(())
 }
+
+/// Prints how many elements of each kind and category were processed.
+fn print_element_summary(elements: &[ProcessedElement]) {
+    let mut kinds: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut categories: BTreeMap<&str, usize> = BTreeMap::new();
+    for element in elements {
+        *kinds.entry(element.kind()).or_default() += 1;
+
+        let category: &str = SUMMARY_CATEGORIES
+            .iter()
+            .find(|key: &&&str| element.tags().contains_key(**key))
+            .copied()
+            .unwrap_or("other");
+        *categories.entry(category).or_default() += 1;
+    }
+
+    let kinds: Vec<String> = kinds
+        .iter()
+        .map(|(kind, count)| format!("{} {}s", count, kind))
+        .collect();
+    println!(
+        "Elements processed: {} ({})",
+        elements.len(),
+        kinds.join(", ")
+    );
+    for (category, count) in categories {
+        println!("  {:<12} {}", category, count);
+    }
+}
+
+/// Named stations, parks, places and similar landmarks, standing on the terrain at their center.
+fn collect_waypoints(elements: &[ProcessedElement], editor: &WorldEditor) -> Vec<Waypoint> {
+    elements
+        .iter()
+        .filter(|element: &&ProcessedElement| {
+            let tags = element.tags();
+            WAYPOINT_TAGS.iter().any(|(key, value)| match value {
+                Some(value) => tags.get(*key).map(|v: &String| v.as_str()) == Some(*value),
+                None => tags.contains_key(*key),
+            })
+        })
+        .filter_map(|element: &ProcessedElement| {
+            let name: &String = element.tags().get("name")?;
+            let (x, z) = element.center()?;
+            Some(Waypoint {
+                name: name.clone(),
+                id: format!("{}_{}", element.kind(), element.id()),
+                x,
+                y: editor.standing_height(x, z),
+                z,
+            })
+        })
+        .collect()
+}
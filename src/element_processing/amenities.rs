@@ -14,12 +14,14 @@ pub fn generate_amenities(
     // Skip if 'layer' or 'level' is negative in the tags
     if let Some(layer) = element.tags().get("layer") {
         if layer.parse::<i32>().unwrap_or(0) < 0 {
+            editor.report_issue("negative layer");
             return;
         }
     }
 
     if let Some(level) = element.tags().get("level") {
         if level.parse::<i32>().unwrap_or(0) < 0 {
+            editor.report_issue("negative level");
             return;
         }
     }
@@ -29,17 +31,37 @@ pub fn generate_amenities(
             .nodes()
             .map(|n: &crate::osm_parser::ProcessedNode| (n.x, n.z))
             .next();
+        let style_block: Option<Block> = editor.style().block("amenity", element.tags());
         match amenity_type.as_str() {
             "waste_disposal" | "waste_basket" => {
                 // Place a cauldron for waste disposal or waste basket
                 if let Some((x, z)) = first_node {
-                    editor.set_block(Block::Cauldron, x, ground_level + 1, z, None, None);
+                    editor.set_scaled_block(
+                        style_block.unwrap_or(Block::Cauldron),
+                        x,
+                        ground_level + 1,
+                        z,
+                        0,
+                        0,
+                        None,
+                        None,
+                    );
                 }
             }
             "vending_machine" | "atm" => {
                 if let Some((x, z)) = first_node {
-                    editor.set_block(Block::IronBlock, x, ground_level + 1, z, None, None);
-                    editor.set_block(Block::IronBlock, x, ground_level + 2, z, None, None);
+                    for y in 1..=editor.scale_height(2) {
+                        editor.set_scaled_block(
+                            style_block.unwrap_or(Block::IronBlock),
+                            x,
+                            ground_level + y,
+                            z,
+                            0,
+                            0,
+                            None,
+                            None,
+                        );
+                    }
                 }
             }
             "bicycle_parking" => {
@@ -52,6 +74,7 @@ pub fn generate_amenities(
                     .collect();
                 let floor_area: Vec<(i32, i32)> =
                     flood_fill_area(&polygon_coords, args.timeout.as_ref());
+                let roof_height: i32 = ground_level + editor.scale_height(4) + 1;
 
                 // Fill the floor area
                 for (x, z) in floor_area.iter() {
@@ -63,32 +86,47 @@ pub fn generate_amenities(
                     let x: i32 = node.x;
                     let z: i32 = node.z;
 
-                    for y in 1..=4 {
+                    for y in 1..=editor.scale_height(4) {
                         editor.set_block(ground_block, x, ground_level, z, None, None);
                         editor.set_block(Block::OakFence, x, ground_level + y, z, None, None);
                     }
-                    editor.set_block(roof_block, x, ground_level + 5, z, None, None);
+                    editor.set_block(roof_block, x, roof_height, z, None, None);
                 }
 
                 // Flood fill the roof area
-                let roof_height: i32 = ground_level + 5;
                 for (x, z) in floor_area.iter() {
                     editor.set_block(roof_block, *x, roof_height, *z, None, None);
                 }
             }
             "bench" => {
-                // Place a bench
+                // Place a bench, a seat of stairs between two armrests
                 if let Some((x, z)) = first_node {
-                    editor.set_block(Block::SmoothStone, x, ground_level + 1, z, None, None);
-                    editor.set_block(Block::OakLog, x + 1, ground_level + 1, z, None, None);
-                    editor.set_block(Block::OakLog, x - 1, ground_level + 1, z, None, None);
+                    let y: i32 = ground_level + 1;
+                    let seat: Block =
+                        match element.tags().get("material").map(|m: &String| m.as_str()) {
+                            Some("stone" | "concrete" | "granite") => Block::StoneBrickStairs,
+                            _ => Block::OakStairs,
+                        };
+                    editor.set_scaled_block(seat, x, y, z, 0, 0, None, None);
+                    editor.set_scaled_block(Block::OakLog, x, y, z, 1, 0, None, None);
+                    editor.set_scaled_block(Block::OakLog, x, y, z, -1, 0, None, None);
                 }
             }
             "vending" => {
                 // Place vending machine blocks
                 if let Some((x, z)) = first_node {
-                    editor.set_block(Block::IronBlock, x, ground_level + 1, z, None, None);
-                    editor.set_block(Block::IronBlock, x, ground_level + 2, z, None, None);
+                    for y in 1..=editor.scale_height(2) {
+                        editor.set_scaled_block(
+                            style_block.unwrap_or(Block::IronBlock),
+                            x,
+                            ground_level + y,
+                            z,
+                            0,
+                            0,
+                            None,
+                            None,
+                        );
+                    }
                 }
             }
             "parking" | "fountain" => {
@@ -97,11 +135,9 @@ pub fn generate_amenities(
                 let mut corner_addup: (i32, i32, i32) = (0, 0, 0);
                 let mut current_amenity: Vec<(i32, i32)> = vec![];
 
-                let block_type = match amenity_type.as_str() {
-                    "fountain" => Block::Water,
-                    "parking" => Block::GrayConcrete,
-                    _ => Block::GrayConcrete,
-                };
+                let block_type: Block = style_block.unwrap_or(Block::GrayConcrete);
+                let border_width: i32 = editor.scale_width(1);
+                let marking_spacing: i32 = editor.scale_width(8);
                 for node in element.nodes() {
                     let x: i32 = node.x;
                     let z: i32 = node.z;
@@ -122,9 +158,9 @@ pub fn generate_amenities(
 
                             // Decorative border around fountains
                             if amenity_type == "fountain" {
-                                for dx in [-1, 0, 1].iter() {
-                                    for dz in [-1, 0, 1].iter() {
-                                        if (*dx, *dz) != (0, 0) {
+                                for dx in -border_width..=border_width {
+                                    for dz in -border_width..=border_width {
+                                        if (dx, dz) != (0, 0) {
                                             editor.set_block(
                                                 Block::LightGrayConcrete,
                                                 bx + dx,
@@ -164,7 +200,10 @@ pub fn generate_amenities(
                         );
 
                         // Add parking spot markings
-                        if amenity_type == "parking" && (x + z) % 8 == 0 && (x * z) % 32 != 0 {
+                        if amenity_type == "parking"
+                            && (x + z) % marking_spacing == 0
+                            && (x * z) % (marking_spacing * 4) != 0
+                        {
                             editor.set_block(
                                 Block::LightGrayConcrete,
                                 x,
@@ -177,7 +216,7 @@ pub fn generate_amenities(
                     }
                 }
             }
-            _ => {}
+            _ => editor.report_issue("unsupported value"),
         }
     }
 }
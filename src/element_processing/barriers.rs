@@ -18,13 +18,22 @@ pub fn generate_barriers(editor: &mut WorldEditor, element: &ProcessedElement, g
                 // Place bollard
             }
         } else if let ProcessedElement::Way(way) = element {
-            // Determine wall height
+            // Determine wall height, from the style if not specified or invalid
+            let default_height: i32 = editor
+                .style()
+                .height("barrier", element.tags())
+                .unwrap_or(2);
             let wall_height: i32 = element
                 .tags()
                 .get("height")
                 .and_then(|height: &String| height.parse::<f32>().ok())
                 .map(|height: f32| f32::min(3.0, height).round() as i32)
-                .unwrap_or(2); // Default height is 2 if not specified or invalid
+                .map(|height: i32| editor.scale_height(height))
+                .unwrap_or_else(|| editor.scale_height(default_height));
+            let wall_block: Block = editor
+                .style()
+                .block("barrier", element.tags())
+                .unwrap_or(Block::CobblestoneWall);
 
             // Process nodes to create the barrier wall
             for i in 1..way.nodes.len() {
@@ -43,7 +52,7 @@ pub fn generate_barriers(editor: &mut WorldEditor, element: &ProcessedElement, g
                 for (bx, _, bz) in bresenham_points {
                     // Build the barrier wall to the specified height
                     for y in (ground_level + 1)..=(ground_level + wall_height) {
-                        editor.set_block(Block::CobblestoneWall, bx, y, bz, None, None);
+                        editor.set_block(wall_block, bx, y, bz, None, None);
                         // Barrier wall
                     }
 
@@ -16,10 +16,13 @@ pub fn generate_buildings(
     args: &Args,
     relation_levels: Option<i32>,
 ) {
+    // Each level is 4 blocks high at a vertical scale of 1
+    let floor_height: i32 = editor.scale_height(4);
+
     // Adjust starting height based on building:min_level
     let start_level = if let Some(min_level_str) = element.tags.get("building:min_level") {
         if let Ok(min_level) = min_level_str.parse::<i32>() {
-            ground_level + (min_level * 4) // Each level is 4 blocks high
+            ground_level.saturating_add(min_level.saturating_mul(floor_height))
         } else {
             ground_level
         }
@@ -62,18 +65,20 @@ pub fn generate_buildings(
 
     // Set to store processed flood fill points
     let mut processed_points: HashSet<(i32, i32)> = HashSet::new();
-    let scale_factor = args.scale;
+    let scale_factor: f64 = args.vertical_scale();
     let mut building_height: i32 = ((6.0 * scale_factor) as i32).max(3); // Default building height with scale and minimum
 
     // Skip if 'layer' or 'level' is negative in the tags
     if let Some(layer) = element.tags.get("layer") {
         if layer.parse::<i32>().unwrap_or(0) < 0 {
+            editor.report_issue("negative layer");
             return;
         }
     }
 
     if let Some(level) = element.tags.get("level") {
         if level.parse::<i32>().unwrap_or(0) < 0 {
+            editor.report_issue("negative level");
             return;
         }
     }
@@ -82,7 +87,7 @@ pub fn generate_buildings(
     if let Some(levels_str) = element.tags.get("building:levels") {
         if let Ok(levels) = levels_str.parse::<i32>() {
             if levels >= 1 {
-                building_height = ((levels * 4 + 2) as f64 * scale_factor) as i32;
+                building_height = ((f64::from(levels) * 4.0 + 2.0) * scale_factor) as i32;
                 building_height = building_height.max(3);
             }
         }
@@ -96,7 +101,7 @@ pub fn generate_buildings(
     }
 
     if let Some(levels) = relation_levels {
-        building_height = ((levels * 4 + 2) as f64 * scale_factor) as i32;
+        building_height = ((f64::from(levels) * 4.0 + 2.0) * scale_factor) as i32;
         building_height = building_height.max(3);
     }
 
@@ -111,20 +116,20 @@ pub fn generate_buildings(
                 .collect();
             let roof_area: Vec<(i32, i32)> =
                 flood_fill_area(&polygon_coords, args.timeout.as_ref());
+            let roof_height: i32 = ground_level + editor.scale_height(4) + 1;
 
             // Place fences and roof slabs at each corner node directly
             for node in &element.nodes {
                 let x: i32 = node.x;
                 let z: i32 = node.z;
 
-                for y in 1..=4 {
+                for y in 1..=editor.scale_height(4) {
                     editor.set_block(Block::OakFence, x, ground_level + y, z, None, None);
                 }
-                editor.set_block(roof_block, x, ground_level + 5, z, None, None);
+                editor.set_block(roof_block, x, roof_height, z, None, None);
             }
 
             // Flood fill the roof area
-            let roof_height: i32 = ground_level + 5;
             for (x, z) in roof_area.iter() {
                 editor.set_block(roof_block, *x, roof_height, *z, None, None);
             }
@@ -150,6 +155,7 @@ pub fn generate_buildings(
                     .collect();
                 let floor_area: Vec<(i32, i32)> =
                     flood_fill_area(&polygon_coords, args.timeout.as_ref());
+                let roof_height: i32 = ground_level + editor.scale_height(4) + 1;
 
                 // Fill the floor area
                 for (x, z) in floor_area.iter() {
@@ -161,15 +167,14 @@ pub fn generate_buildings(
                     let x: i32 = node.x;
                     let z: i32 = node.z;
 
-                    for y in 1..=4 {
+                    for y in 1..=editor.scale_height(4) {
                         editor.set_block(ground_block, x, ground_level, z, None, None);
                         editor.set_block(Block::OakFence, x, ground_level + y, z, None, None);
                     }
-                    editor.set_block(roof_block, x, ground_level + 5, z, None, None);
+                    editor.set_block(roof_block, x, roof_height, z, None, None);
                 }
 
                 // Flood fill the roof area
-                let roof_height: i32 = ground_level + 5;
                 for (x, z) in floor_area.iter() {
                     editor.set_block(roof_block, *x, roof_height, *z, None, None);
                 }
@@ -185,7 +190,7 @@ pub fn generate_buildings(
             // Parking building structure
 
             // Ensure minimum height
-            building_height = building_height.max(16);
+            building_height = building_height.max(floor_height * 4);
 
             let polygon_coords: Vec<(i32, i32)> = element
                 .nodes
@@ -195,8 +200,8 @@ pub fn generate_buildings(
             let floor_area: Vec<(i32, i32)> =
                 flood_fill_area(&polygon_coords, args.timeout.as_ref());
 
-            for level in 0..=(building_height / 4) {
-                let current_level = ground_level + level * 4;
+            for level in 0..=(building_height / floor_height) {
+                let current_level = ground_level + level * floor_height;
 
                 // Build walls
                 for node in &element.nodes {
@@ -204,7 +209,7 @@ pub fn generate_buildings(
                     let z: i32 = node.z;
 
                     // Build walls up to the current level
-                    for y in (current_level + 1)..=(current_level + 4) {
+                    for y in (current_level + 1)..=(current_level + floor_height) {
                         editor.set_block(Block::StoneBricks, x, y, z, None, None);
                     }
                 }
@@ -220,8 +225,8 @@ pub fn generate_buildings(
             }
 
             // Outline for each level
-            for level in 0..=(building_height / 4) {
-                let current_level = ground_level + level * 4;
+            for level in 0..=(building_height / floor_height) {
+                let current_level = ground_level + level * floor_height;
 
                 // Use the nodes to create the outline
                 let mut prev_outline = None;
@@ -267,7 +272,7 @@ pub fn generate_buildings(
 
             return;
         } else if building_type == "roof" {
-            let roof_height: i32 = ground_level + 5;
+            let roof_height: i32 = ground_level + editor.scale_height(4) + 1;
 
             // Iterate through the nodes to create the roof edges using Bresenham's line algorithm
             for node in &element.nodes {
@@ -322,6 +327,13 @@ pub fn generate_buildings(
         }
     }
 
+    // Keep the roof and its outline below the build limit
+    let max_building_height: i32 = args.mc_version.max_build_height() - start_level - 2;
+    if building_height > max_building_height {
+        editor.report_issue("capped at the build limit");
+        building_height = max_building_height.max(0);
+    }
+
     // Process nodes to create walls and corners
     for node in &element.nodes {
         let x: i32 = node.x;
@@ -337,7 +349,7 @@ pub fn generate_buildings(
                         editor.set_block(corner_block, bx, h, bz, None, None); // Corner block
                     } else {
                         // Add windows to the walls at intervals
-                        if h > start_level + 1 && h % 4 != 0 && (bx + bz) % 6 < 3 {
+                        if h > start_level + 1 && h % floor_height != 0 && (bx + bz) % 6 < 3 {
                             editor.set_block(window_block, bx, h, bz, None, None);
                         // Window block
                         } else {
@@ -389,8 +401,10 @@ pub fn generate_buildings(
                 editor.set_block(floor_block, x, start_level, z, None, None); // Set floor
 
                 // Set level ceilings if height > 4
-                if building_height > 4 {
-                    for h in (start_level + 2 + 4..start_level + building_height).step_by(4) {
+                if building_height > floor_height {
+                    for h in (start_level + 2 + floor_height..start_level + building_height)
+                        .step_by(floor_height as usize)
+                    {
                         if x % 6 == 0 && z % 6 == 0 {
                             editor.set_block(Block::Glowstone, x, h, z, None, None);
                         // Light fixtures
@@ -498,7 +512,7 @@ fn generate_bridge(
     let mut bridge_level: i32 = base_level;
     if let Some(level_str) = element.tags.get("level") {
         if let Ok(level) = level_str.parse::<i32>() {
-            bridge_level += (level * 3) + 1; // Adjust height by levels
+            bridge_level += editor.scale_height(level * 3) + 1; // Adjust height by levels
         }
     }
 
@@ -9,6 +9,7 @@ pub fn generate_doors(editor: &mut WorldEditor, element: &ProcessedNode, ground_
         if let Some(level_str) = element.tags.get("level") {
             if let Ok(level) = level_str.parse::<i32>() {
                 if level != 0 {
+                    editor.report_issue("not on the ground level");
                     return; // Skip doors not on ground level
                 }
             }
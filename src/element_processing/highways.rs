@@ -17,10 +17,18 @@ pub fn generate_highways(
             if let ProcessedElement::Node(first_node) = element {
                 let x: i32 = first_node.x;
                 let z: i32 = first_node.z;
-                for y in 1..=4 {
+                let pole_height: i32 = editor.scale_height(4);
+                for y in 1..=pole_height {
                     editor.set_block(Block::OakFence, x, ground_level + y, z, None, None);
                 }
-                editor.set_block(Block::Glowstone, x, ground_level + 5, z, None, None);
+                editor.set_block(
+                    Block::Glowstone,
+                    x,
+                    ground_level + pole_height + 1,
+                    z,
+                    None,
+                    None,
+                );
             }
         } else if highway_type == "crossing" {
             // Handle traffic signals for crossings
@@ -29,7 +37,8 @@ pub fn generate_highways(
                     if let ProcessedElement::Node(node) = element {
                         let x: i32 = node.x;
                         let z: i32 = node.z;
-                        for y in 1..=3 {
+                        let pole_height: i32 = editor.scale_height(3);
+                        for y in 1..=pole_height {
                             editor.set_block(
                                 Block::CobblestoneWall,
                                 x,
@@ -40,12 +49,13 @@ pub fn generate_highways(
                             );
                         }
 
-                        editor.set_block(Block::GreenWool, x, ground_level + 4, z, None, None);
-                        editor.set_block(Block::YellowWool, x, ground_level + 5, z, None, None);
-                        editor.set_block(Block::RedWool, x, ground_level + 6, z, None, None);
+                        let lights_level: i32 = ground_level + pole_height;
+                        editor.set_block(Block::GreenWool, x, lights_level + 1, z, None, None);
+                        editor.set_block(Block::YellowWool, x, lights_level + 2, z, None, None);
+                        editor.set_block(Block::RedWool, x, lights_level + 3, z, None, None);
 
                         if args.winter {
-                            editor.set_block(Block::SnowLayer, x, ground_level + 7, z, None, None);
+                            editor.set_block(Block::SnowLayer, x, lights_level + 4, z, None, None);
                         }
                     }
                 }
@@ -55,12 +65,14 @@ pub fn generate_highways(
             if let ProcessedElement::Node(node) = element {
                 let x: i32 = node.x;
                 let z: i32 = node.z;
-                for y in 1..=3 {
+                let pole_height: i32 = editor.scale_height(3);
+                for y in 1..=pole_height {
                     editor.set_block(Block::CobblestoneWall, x, ground_level + y, z, None, None);
                 }
 
-                editor.set_block(Block::WhiteWool, x, ground_level + 4, z, None, None);
-                editor.set_block(Block::WhiteWool, x + 1, ground_level + 4, z, None, None);
+                let sign_level: i32 = ground_level + pole_height + 1;
+                editor.set_scaled_block(Block::WhiteWool, x, sign_level, z, 0, 0, None, None);
+                editor.set_scaled_block(Block::WhiteWool, x, sign_level, z, 1, 0, None, None);
             }
         } else if element
             .tags()
@@ -71,30 +83,11 @@ pub fn generate_highways(
                 return;
             };
 
-            // Handle areas like pedestrian plazas
-            let mut surface_block: Block = Block::Stone; // Default block
-
-            // Determine the block type based on the 'surface' tag
-            if let Some(surface) = element.tags().get("surface") {
-                surface_block = match surface.as_str() {
-                    "paving_stones" | "sett" => Block::StoneBricks,
-                    "bricks" => Block::Brick,
-                    "wood" => Block::OakPlanks,
-                    "asphalt" => Block::BlackConcrete,
-                    "gravel" | "fine_gravel" => Block::Gravel,
-                    "grass" => {
-                        if args.winter {
-                            Block::SnowBlock
-                        } else {
-                            Block::GrassBlock
-                        }
-                    }
-                    "dirt" => Block::Dirt,
-                    "sand" => Block::Sand,
-                    "concrete" => Block::LightGrayConcrete,
-                    _ => Block::Stone, // Default to stone for unknown surfaces
-                };
-            }
+            // Handle areas like pedestrian plazas, in the block of their surface
+            let surface_block: Block = editor
+                .style()
+                .block("highway", element.tags())
+                .unwrap_or(Block::Stone);
 
             // Fill the area using flood fill or by iterating through the nodes
             let polygon_coords: Vec<(i32, i32)> = way
@@ -110,61 +103,41 @@ pub fn generate_highways(
             }
         } else {
             let mut previous_node: Option<(i32, i32)> = None;
-            let mut block_type = Block::BlackConcrete;
-            let mut block_range: i32 = 2;
-            let mut add_stripe = false;
 
             // Skip if 'layer' or 'level' is negative in the tags
             if let Some(layer) = element.tags().get("layer") {
                 if layer.parse::<i32>().unwrap_or(0) < 0 {
+                    editor.report_issue("negative layer");
                     return;
                 }
             }
 
             if let Some(level) = element.tags().get("level") {
                 if level.parse::<i32>().unwrap_or(0) < 0 {
+                    editor.report_issue("negative level");
                     return;
                 }
             }
 
-            // Determine block type and range based on highway type
-            match highway_type.as_str() {
-                "footway" | "pedestrian" => {
-                    block_type = Block::GrayConcrete;
-                    block_range = 1;
-                }
-                "path" => {
-                    block_type = Block::LightGrayConcrete;
-                    block_range = 1;
-                }
-                "motorway" | "primary" => {
-                    block_range = 5;
-                    add_stripe = true; // Add stripes for motorways and primary roads
-                }
-                "track" => {
-                    block_range = 1;
-                }
-                "service" => {
-                    block_type = Block::GrayConcrete;
-                    block_range = 2;
-                }
-                _ => {
-                    if let Some(lanes) = element.tags().get("lanes") {
-                        if lanes == "2" {
-                            block_range = 3;
-                            add_stripe = true;
-                        } else if lanes != "1" {
-                            block_range = 4;
-                            add_stripe = true;
-                        }
-                    }
-                }
-            }
+            // Determine block type, width and markings based on the highway tags
+            let block_type: Block = editor
+                .style()
+                .block("highway", element.tags())
+                .unwrap_or(Block::BlackConcrete);
+            let width: i32 = editor.style().width("highway", element.tags()).unwrap_or(5);
+            let add_stripe: bool = editor
+                .style()
+                .markings("highway", element.tags())
+                .unwrap_or(false);
 
             let ProcessedElement::Way(way) = element else {
                 return;
             };
 
+            // Scale the width of the road, keeping it centered on the way
+            let block_range: i32 = (editor.scale_width(width) - 1) / 2;
+            let zebra_stripe_width: i32 = editor.scale_width(1);
+
             // Iterate over nodes to create the highway
             for node in &way.nodes {
                 if let Some(prev) = previous_node {
@@ -178,8 +151,8 @@ pub fn generate_highways(
 
                     // Variables to manage dashed line pattern
                     let mut stripe_length: i32 = 0;
-                    let dash_length: i32 = 5; // Length of the solid part of the stripe
-                    let gap_length: i32 = 5; // Length of the gap part of the stripe
+                    let dash_length: i32 = editor.scale_width(5); // Length of the solid part of the stripe
+                    let gap_length: i32 = editor.scale_width(5); // Length of the gap part of the stripe
 
                     for (x, _, z) in bresenham_points {
                         // Draw the road surface for the entire width
@@ -195,7 +168,7 @@ pub fn generate_highways(
                                 {
                                     let is_horizontal: bool = (x2 - x1).abs() >= (z2 - z1).abs();
                                     if is_horizontal {
-                                        if set_x % 2 < 1 {
+                                        if (set_x / zebra_stripe_width) % 2 < 1 {
                                             editor.set_block(
                                                 Block::WhiteConcrete,
                                                 set_x,
@@ -214,7 +187,7 @@ pub fn generate_highways(
                                                 None,
                                             );
                                         }
-                                    } else if set_z % 2 < 1 {
+                                    } else if (set_z / zebra_stripe_width) % 2 < 1 {
                                         editor.set_block(
                                             Block::WhiteConcrete,
                                             set_x,
@@ -309,14 +282,15 @@ pub fn generate_siding(editor: &mut WorldEditor, element: &ProcessedWay, ground_
 pub fn generate_aeroway(editor: &mut WorldEditor, way: &ProcessedWay, ground_level: i32) {
     let mut previous_node: Option<(i32, i32)> = None;
     let surface_block = Block::LightGrayConcrete;
+    let (from, to) = (editor.scale_width(-12), editor.scale_width(2) - 1);
 
     for node in &way.nodes {
         if let Some(prev) = previous_node {
             let points = bresenham_line(prev.0, ground_level, prev.1, node.x, ground_level, node.z);
 
             for (x, y, z) in points {
-                for dx in -12..=1 {
-                    for dz in -12..=1 {
+                for dx in from..=to {
+                    for dz in from..=to {
                         editor.set_block(surface_block, x + dx, y, z + dz, None, None);
                     }
                 }
@@ -1,4 +1,5 @@
 use crate::args::Args;
+use crate::biome_definitions::Biome;
 use crate::block_definitions::Block;
 use crate::bresenham::bresenham_line;
 use crate::element_processing::tree::create_tree;
@@ -21,41 +22,20 @@ pub fn generate_landuse(
     let binding: String = "".to_string();
     let landuse_tag: &String = element.tags.get("landuse").unwrap_or(&binding);
 
-    let block_type = match landuse_tag.as_str() {
-        "greenfield" | "meadow" | "grass" => {
-            if args.winter {
-                Block::SnowBlock
-            } else {
-                Block::GrassBlock
-            }
-        }
-        "farmland" => Block::Farmland,
-        "forest" => {
-            if args.winter {
-                Block::SnowBlock
-            } else {
-                Block::GrassBlock
-            }
-        }
-        "cemetery" => Block::Podzol,
-        "beach" => Block::Sand,
-        "construction" => Block::Dirt,
-        "traffic_island" => Block::StoneBlockSlab,
-        "residential" => Block::StoneBricks,
-        "commercial" => Block::SmoothStone,
-        "education" => Block::LightGrayConcrete,
-        "industrial" => Block::Cobblestone,
-        "military" => Block::GrayConcrete,
-        "railway" => Block::Gravel,
-        _ => {
-            if args.winter {
-                Block::SnowBlock
-            } else {
-                Block::GrassBlock
-            }
+    let block_type: Block = match editor.style().block("landuse", &element.tags) {
+        Some(block) => block,
+        None => {
+            editor.report_issue("unknown value, generated as grass");
+            editor.style().seasonal(Block::GrassBlock)
         }
     };
 
+    let biome: Option<Biome> = match landuse_tag.as_str() {
+        "forest" => Some(Biome::Forest),
+        "beach" => Some(Biome::Beach),
+        _ => None,
+    };
+
     let bresenham_block: Block = if args.winter {
         Block::SnowBlock
     } else {
@@ -98,10 +78,15 @@ pub fn generate_landuse(
                 editor.set_block(block_type, x, ground_level, z, None, None);
             }
 
+            if let Some(biome) = biome {
+                editor.set_biome(biome, x, z);
+            }
+
             // Add specific features for different landuse types
             match landuse_tag.as_str() {
                 "cemetery" => {
-                    if (x % 3 == 0) && (z % 3 == 0) {
+                    let grave_spacing: i32 = editor.scale_width(3);
+                    if (x % grave_spacing == 0) && (z % grave_spacing == 0) {
                         let random_choice: i32 = rng.gen_range(0..100);
                         if random_choice < 15 {
                             // Place graves
@@ -113,68 +98,84 @@ pub fn generate_landuse(
                                 None,
                             ) {
                                 if rng.gen_bool(0.5) {
-                                    editor.set_block(
+                                    editor.set_scaled_block(
                                         Block::Cobblestone,
-                                        x - 1,
+                                        x,
                                         ground_level + 1,
                                         z,
+                                        -1,
+                                        0,
                                         None,
                                         None,
                                     );
-                                    editor.set_block(
+                                    editor.set_scaled_block(
                                         Block::StoneBrickSlab,
-                                        x - 1,
+                                        x,
                                         ground_level + 2,
                                         z,
+                                        -1,
+                                        0,
                                         None,
                                         None,
                                     );
-                                    editor.set_block(
+                                    editor.set_scaled_block(
                                         Block::StoneBrickSlab,
                                         x,
                                         ground_level + 1,
                                         z,
+                                        0,
+                                        0,
                                         None,
                                         None,
                                     );
-                                    editor.set_block(
+                                    editor.set_scaled_block(
                                         Block::StoneBrickSlab,
-                                        x + 1,
+                                        x,
                                         ground_level + 1,
                                         z,
+                                        1,
+                                        0,
                                         None,
                                         None,
                                     );
                                 } else {
-                                    editor.set_block(
+                                    editor.set_scaled_block(
                                         Block::Cobblestone,
                                         x,
                                         ground_level + 1,
-                                        z - 1,
+                                        z,
+                                        0,
+                                        -1,
                                         None,
                                         None,
                                     );
-                                    editor.set_block(
+                                    editor.set_scaled_block(
                                         Block::StoneBrickSlab,
                                         x,
                                         ground_level + 2,
-                                        z - 1,
+                                        z,
+                                        0,
+                                        -1,
                                         None,
                                         None,
                                     );
-                                    editor.set_block(
+                                    editor.set_scaled_block(
                                         Block::StoneBrickSlab,
                                         x,
                                         ground_level + 1,
                                         z,
+                                        0,
+                                        0,
                                         None,
                                         None,
                                     );
-                                    editor.set_block(
+                                    editor.set_scaled_block(
                                         Block::StoneBrickSlab,
                                         x,
                                         ground_level + 1,
-                                        z + 1,
+                                        z,
+                                        0,
+                                        1,
                                         None,
                                         None,
                                     );
@@ -237,7 +238,8 @@ pub fn generate_landuse(
                 "farmland" => {
                     // Check if the current block is not water or another undesired block
                     if !editor.check_for_block(x, ground_level, z, None, Some(&[Block::Water])) {
-                        if x % 15 == 0 || z % 15 == 0 {
+                        let ditch_spacing: i32 = editor.scale_width(15);
+                        if x % ditch_spacing == 0 || z % ditch_spacing == 0 {
                             // Place water on the edges
                             editor.set_block(
                                 Block::Water,
@@ -323,103 +325,38 @@ pub fn generate_landuse(
                 "construction" => {
                     let random_choice: i32 = rng.gen_range(0..1501);
                     if random_choice < 6 {
-                        editor.set_block(Block::Scaffolding, x, ground_level + 1, z, None, None);
-                        if random_choice < 2 {
-                            editor.set_block(
-                                Block::Scaffolding,
-                                x,
-                                ground_level + 2,
-                                z,
-                                None,
-                                None,
-                            );
-                            editor.set_block(
-                                Block::Scaffolding,
-                                x,
-                                ground_level + 3,
-                                z,
-                                None,
-                                None,
-                            );
-                        } else if random_choice < 4 {
-                            editor.set_block(
-                                Block::Scaffolding,
-                                x,
-                                ground_level + 2,
-                                z,
-                                None,
-                                None,
-                            );
-                            editor.set_block(
-                                Block::Scaffolding,
-                                x,
-                                ground_level + 3,
-                                z,
-                                None,
-                                None,
-                            );
-                            editor.set_block(
-                                Block::Scaffolding,
-                                x,
-                                ground_level + 4,
-                                z,
-                                None,
-                                None,
-                            );
-                            editor.set_block(
-                                Block::Scaffolding,
-                                x,
-                                ground_level + 1,
-                                z + 1,
-                                None,
-                                None,
-                            );
-                        } else {
-                            editor.set_block(
-                                Block::Scaffolding,
-                                x,
-                                ground_level + 2,
-                                z,
-                                None,
-                                None,
-                            );
-                            editor.set_block(
-                                Block::Scaffolding,
-                                x,
-                                ground_level + 3,
-                                z,
-                                None,
-                                None,
-                            );
-                            editor.set_block(
+                        // A scaffolding tower, the taller ones with scaffolding next to them
+                        let tower_height: i32 = editor.scale_height(match random_choice {
+                            0..=1 => 3,
+                            2..=3 => 4,
+                            _ => 5,
+                        });
+                        for y in 1..=tower_height {
+                            editor.set_scaled_block(
                                 Block::Scaffolding,
                                 x,
-                                ground_level + 4,
+                                ground_level + y,
                                 z,
+                                0,
+                                0,
                                 None,
                                 None,
                             );
-                            editor.set_block(
+                        }
+
+                        let sides: &[(i32, i32)] = match random_choice {
+                            0..=1 => &[],
+                            2..=3 => &[(0, 1)],
+                            _ => &[(-1, 0), (1, -1)],
+                        };
+                        for (dx, dz) in sides {
+                            editor.set_scaled_block(
                                 Block::Scaffolding,
                                 x,
-                                ground_level + 5,
-                                z,
-                                None,
-                                None,
-                            );
-                            editor.set_block(
-                                Block::Scaffolding,
-                                x - 1,
                                 ground_level + 1,
                                 z,
-                                None,
-                                None,
-                            );
-                            editor.set_block(
-                                Block::Scaffolding,
-                                x + 1,
-                                ground_level + 1,
-                                z - 1,
+                                *dx,
+                                *dz,
                                 None,
                                 None,
                             );
@@ -447,17 +384,47 @@ pub fn generate_landuse(
                             None,
                         );
                     } else if random_choice < 35 {
-                        if random_choice < 30 {
-                            editor.set_block(Block::Dirt, x, ground_level + 1, z, None, None);
-                            editor.set_block(Block::Dirt, x, ground_level + 2, z, None, None);
-                            editor.set_block(Block::Dirt, x + 1, ground_level + 1, z, None, None);
-                            editor.set_block(Block::Dirt, x, ground_level + 1, z + 1, None, None);
-                        } else {
-                            editor.set_block(Block::Dirt, x, ground_level + 1, z, None, None);
-                            editor.set_block(Block::Dirt, x, ground_level + 2, z, None, None);
-                            editor.set_block(Block::Dirt, x - 1, ground_level + 1, z, None, None);
-                            editor.set_block(Block::Dirt, x, ground_level + 1, z - 1, None, None);
-                        }
+                        // A pile of dirt
+                        editor.set_scaled_block(
+                            Block::Dirt,
+                            x,
+                            ground_level + 1,
+                            z,
+                            0,
+                            0,
+                            None,
+                            None,
+                        );
+                        editor.set_scaled_block(
+                            Block::Dirt,
+                            x,
+                            ground_level + 2,
+                            z,
+                            0,
+                            0,
+                            None,
+                            None,
+                        );
+                        editor.set_scaled_block(
+                            Block::Dirt,
+                            x,
+                            ground_level + 1,
+                            z,
+                            -1,
+                            0,
+                            None,
+                            None,
+                        );
+                        editor.set_scaled_block(
+                            Block::Dirt,
+                            x,
+                            ground_level + 1,
+                            z,
+                            0,
+                            -1,
+                            None,
+                            None,
+                        );
                     } else if random_choice < 150 {
                         editor.set_block(
                             Block::Air,
@@ -19,40 +19,11 @@ pub fn generate_leisure(
         let mut current_leisure: Vec<(i32, i32)> = vec![];
 
         // Determine block type based on leisure type
-        let block_type: Block = match leisure_type.as_str() {
-            "park" => {
-                if args.winter {
-                    Block::SnowBlock
-                } else {
-                    Block::GrassBlock
-                }
-            }
-            "playground" | "recreation_ground" | "pitch" => {
-                if let Some(surface) = element.tags.get("surface") {
-                    match surface.as_str() {
-                        "clay" => Block::Terracotta,
-                        "sand" => Block::Sand,
-                        "tartan" => Block::RedTerracotta,
-                        _ => Block::GreenStainedHardenedClay,
-                    }
-                } else {
-                    Block::GreenStainedHardenedClay
-                }
-            }
-            "garden" => {
-                if args.winter {
-                    Block::SnowBlock
-                } else {
-                    Block::GrassBlock
-                }
-            }
-            "swimming_pool" => Block::Water,
-            _ => {
-                if args.winter {
-                    Block::SnowBlock
-                } else {
-                    Block::GrassBlock
-                }
+        let block_type: Block = match editor.style().block("leisure", &element.tags) {
+            Some(block) => block,
+            None => {
+                editor.report_issue("unknown value, generated as grass");
+                editor.style().seasonal(Block::GrassBlock)
             }
         };
 
@@ -118,9 +89,10 @@ pub fn generate_leisure(
                     match random_choice {
                         0 => {
                             // Benches
-                            editor.set_block(Block::OakLog, x, ground_level + 1, z, None, None);
-                            editor.set_block(Block::OakLog, x + 1, ground_level + 1, z, None, None);
-                            editor.set_block(Block::OakLog, x - 1, ground_level + 1, z, None, None);
+                            let y: i32 = ground_level + 1;
+                            editor.set_scaled_block(Block::OakLog, x, y, z, 0, 0, None, None);
+                            editor.set_scaled_block(Block::OakLog, x, y, z, 1, 0, None, None);
+                            editor.set_scaled_block(Block::OakLog, x, y, z, -1, 0, None, None);
                         }
                         1..=30 => {
                             // Flowers
@@ -159,98 +131,100 @@ pub fn generate_leisure(
                     match random_choice {
                         0..=10 => {
                             // Swing set
-                            for y in 1..=4 {
-                                editor.set_block(
+                            let frame_height: i32 = editor.scale_height(4);
+                            for y in 1..=frame_height {
+                                editor.set_scaled_block(
                                     Block::OakFence,
-                                    x - 1,
+                                    x,
                                     ground_level + y,
                                     z,
+                                    -1,
+                                    0,
                                     None,
                                     None,
                                 );
-                                editor.set_block(
+                                editor.set_scaled_block(
                                     Block::OakFence,
-                                    x + 1,
+                                    x,
                                     ground_level + y,
                                     z,
+                                    1,
+                                    0,
                                     None,
                                     None,
                                 );
                             }
-                            editor.set_block(Block::OakFence, x, ground_level + 4, z, None, None);
-                            editor.set_block(
-                                Block::StoneBlockSlab,
+                            editor.set_scaled_block(
+                                Block::OakFence,
                                 x,
-                                ground_level + 2,
-                                z,
-                                None,
-                                None,
-                            );
-                        }
-                        11..=20 => {
-                            // Slide
-                            editor.set_block(Block::OakSlab, x, ground_level + 1, z, None, None);
-                            editor.set_block(
-                                Block::OakSlab,
-                                x + 1,
-                                ground_level + 2,
+                                ground_level + frame_height,
                                 z,
+                                0,
+                                0,
                                 None,
                                 None,
                             );
-                            editor.set_block(
-                                Block::OakSlab,
-                                x + 2,
-                                ground_level + 3,
-                                z,
-                                None,
-                                None,
-                            );
-
-                            editor.set_block(
-                                Block::OakPlanks,
-                                x + 2,
-                                ground_level + 2,
-                                z,
-                                None,
-                                None,
-                            );
-                            editor.set_block(
-                                Block::OakPlanks,
-                                x + 2,
-                                ground_level + 1,
+                            editor.set_scaled_block(
+                                Block::StoneBlockSlab,
+                                x,
+                                ground_level + editor.scale_height(2),
                                 z,
+                                0,
+                                0,
                                 None,
                                 None,
                             );
+                        }
+                        11..=20 => {
+                            // Slide, rising one step per block towards the ladder
+                            for step in 0..3 {
+                                editor.set_scaled_block(
+                                    Block::OakSlab,
+                                    x,
+                                    ground_level + editor.scale_height(step + 1),
+                                    z,
+                                    step,
+                                    0,
+                                    None,
+                                    None,
+                                );
+                            }
 
-                            editor.set_block(
-                                Block::Ladder,
-                                x + 2,
-                                ground_level + 2,
-                                z - 1,
-                                None,
-                                None,
-                            );
-                            editor.set_block(
-                                Block::Ladder,
-                                x + 2,
-                                ground_level + 1,
-                                z - 1,
-                                None,
-                                None,
-                            );
+                            // Platform under the top of the slide with a ladder up to it
+                            for y in 1..editor.scale_height(3) {
+                                editor.set_scaled_block(
+                                    Block::OakPlanks,
+                                    x,
+                                    ground_level + y,
+                                    z,
+                                    2,
+                                    0,
+                                    None,
+                                    None,
+                                );
+                                editor.set_scaled_block(
+                                    Block::Ladder,
+                                    x,
+                                    ground_level + y,
+                                    z,
+                                    2,
+                                    -1,
+                                    None,
+                                    None,
+                                );
+                            }
                         }
                         21..=30 => {
                             // Sandpit
+                            let (from, to) = (editor.scale_width(-3), editor.scale_width(4) - 1);
                             editor.fill_blocks(
                                 Block::Sand,
-                                x - 3,
+                                x + from,
                                 ground_level,
-                                z - 3,
-                                x + 3,
+                                z + from,
+                                x + to,
                                 ground_level,
-                                z + 3,
+                                z + to,
                                 Some(&[Block::GreenStainedHardenedClay]),
                                 None,
                             );
@@ -1,4 +1,5 @@
 use crate::args::Args;
+use crate::biome_definitions::{water_biome, Biome};
 use crate::block_definitions::Block;
 use crate::bresenham::bresenham_line;
 use crate::element_processing::tree::create_tree;
@@ -35,32 +36,22 @@ pub fn generate_natural(
             let mut current_natural: Vec<(i32, i32)> = vec![];
 
             // Determine block type based on natural tag
-            let block_type: Block = match natural_type.as_str() {
-                "scrub" | "grassland" | "wood" => {
-                    if args.winter {
-                        Block::SnowBlock
-                    } else {
-                        Block::GrassBlock
-                    }
-                }
-                "beach" | "sand" => Block::Sand,
-                "tree_row" => {
-                    if args.winter {
-                        Block::SnowBlock
-                    } else {
-                        Block::GrassBlock
-                    }
-                }
-                "wetland" | "water" => Block::Water,
-                _ => {
-                    if args.winter {
-                        Block::SnowBlock
-                    } else {
-                        Block::GrassBlock
-                    }
+            let block_type: Block = match editor.style().block("natural", element.tags()) {
+                Some(block) => block,
+                None => {
+                    editor.report_issue("unknown value, generated as grass");
+                    editor.style().seasonal(Block::GrassBlock)
                 }
             };
 
+            let biome: Option<Biome> = match natural_type.as_str() {
+                "wood" => Some(Biome::Forest),
+                "beach" | "sand" => Some(Biome::Beach),
+                "wetland" => Some(Biome::Swamp),
+                "water" => Some(water_biome(element.tags())),
+                _ => None,
+            };
+
             let ProcessedElement::Way(way) = element else {
                 return;
             };
@@ -99,6 +90,9 @@ pub fn generate_natural(
 
                 for (x, z) in filled_area {
                     editor.set_block(block_type, x, ground_level, z, None, None);
+                    if let Some(biome) = biome {
+                        editor.set_biome(biome, x, z);
+                    }
 
                     // Generate elements for "wood" and "tree_row"
                     if natural_type == "wood" || natural_type == "tree_row" {
@@ -35,7 +35,6 @@ pub fn generate_railways(editor: &mut WorldEditor, element: &ProcessedWay, groun
                 bresenham_line(x1, ground_level, z1, x2, ground_level, z2);
 
             for (bx, _, bz) in bresenham_points {
-                // TODO: Set direction of rail
                 editor.set_block(Block::IronBlock, bx, ground_level, bz, None, None);
                 editor.set_block(Block::Rail, bx, ground_level + 1, bz, None, None);
 
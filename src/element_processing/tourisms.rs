@@ -1,17 +1,20 @@
 use crate::block_definitions::Block;
 use crate::osm_parser::ProcessedNode;
+use crate::world_editor::block_entities::Sign;
 use crate::world_editor::WorldEditor;
 
 pub fn generate_tourisms(editor: &mut WorldEditor, element: &ProcessedNode, ground_level: i32) {
     // Skip if 'layer' or 'level' is negative in the tags
     if let Some(layer) = element.tags.get("layer") {
         if layer.parse::<i32>().unwrap_or(0) < 0 {
+            editor.report_issue("negative layer");
             return;
         }
     }
 
     if let Some(level) = element.tags.get("level") {
         if level.parse::<i32>().unwrap_or(0) < 0 {
+            editor.report_issue("negative level");
             return;
         }
     }
@@ -22,8 +25,29 @@ pub fn generate_tourisms(editor: &mut WorldEditor, element: &ProcessedNode, grou
 
         if tourism_type == "information" {
             if let Some("board") = element.tags.get("information").map(|x: &String| x.as_str()) {
-                // TODO draw a sign
-                editor.set_block(Block::OakPlanks, x, ground_level + 1, z, None, None);
+                let board_height: i32 = editor.scale_height(1);
+                for y in 1..=board_height {
+                    editor.set_scaled_block(
+                        Block::OakPlanks,
+                        x,
+                        ground_level + y,
+                        z,
+                        0,
+                        0,
+                        None,
+                        None,
+                    );
+                }
+
+                // Show the name of the board on a sign on top of it
+                if let Some(name) = element.tags.get("name") {
+                    editor.set_block_entity(
+                        Sign::wrapped(name),
+                        x,
+                        ground_level + board_height + 1,
+                        z,
+                    );
+                }
             }
         }
     }
@@ -1,48 +1,70 @@
 use crate::block_definitions::*;
 use crate::world_editor::WorldEditor;
+use std::ops::Range;
 
 /// Helper function to set blocks in a circular pattern around a central point.
-fn round1(editor: &mut WorldEditor, material: Block, x: i32, y: i32, z: i32) {
-    editor.set_block(material, x - 2, y, z, None, None);
-    editor.set_block(material, x + 2, y, z, None, None);
-    editor.set_block(material, x, y, z - 2, None, None);
-    editor.set_block(material, x, y, z + 2, None, None);
-    editor.set_block(material, x - 1, y, z - 1, None, None);
-    editor.set_block(material, x + 1, y, z + 1, None, None);
-    editor.set_block(material, x + 1, y, z - 1, None, None);
-    editor.set_block(material, x - 1, y, z + 1, None, None);
+fn round1(editor: &mut WorldEditor, material: Block, x: i32, layers: Range<i32>, z: i32) {
+    for y in layers {
+        editor.set_scaled_block(material, x, y, z, -2, 0, None, None);
+        editor.set_scaled_block(material, x, y, z, 2, 0, None, None);
+        editor.set_scaled_block(material, x, y, z, 0, -2, None, None);
+        editor.set_scaled_block(material, x, y, z, 0, 2, None, None);
+        editor.set_scaled_block(material, x, y, z, -1, -1, None, None);
+        editor.set_scaled_block(material, x, y, z, 1, 1, None, None);
+        editor.set_scaled_block(material, x, y, z, 1, -1, None, None);
+        editor.set_scaled_block(material, x, y, z, -1, 1, None, None);
+    }
 }
 
 /// Helper function to set blocks in a wider circular pattern.
-fn round2(editor: &mut WorldEditor, material: Block, x: i32, y: i32, z: i32) {
-    editor.set_block(material, x + 3, y, z, None, None);
-    editor.set_block(material, x + 2, y, z - 1, None, None);
-    editor.set_block(material, x + 2, y, z + 1, None, None);
-    editor.set_block(material, x + 1, y, z - 2, None, None);
-    editor.set_block(material, x + 1, y, z + 2, None, None);
-    editor.set_block(material, x - 3, y, z, None, None);
-    editor.set_block(material, x - 2, y, z - 1, None, None);
-    editor.set_block(material, x - 2, y, z + 1, None, None);
-    editor.set_block(material, x - 1, y, z + 2, None, None);
-    editor.set_block(material, x - 1, y, z - 2, None, None);
-    editor.set_block(material, x, y, z - 3, None, None);
-    editor.set_block(material, x, y, z + 3, None, None);
+fn round2(editor: &mut WorldEditor, material: Block, x: i32, layers: Range<i32>, z: i32) {
+    for y in layers {
+        editor.set_scaled_block(material, x, y, z, 3, 0, None, None);
+        editor.set_scaled_block(material, x, y, z, 2, -1, None, None);
+        editor.set_scaled_block(material, x, y, z, 2, 1, None, None);
+        editor.set_scaled_block(material, x, y, z, 1, -2, None, None);
+        editor.set_scaled_block(material, x, y, z, 1, 2, None, None);
+        editor.set_scaled_block(material, x, y, z, -3, 0, None, None);
+        editor.set_scaled_block(material, x, y, z, -2, -1, None, None);
+        editor.set_scaled_block(material, x, y, z, -2, 1, None, None);
+        editor.set_scaled_block(material, x, y, z, -1, 2, None, None);
+        editor.set_scaled_block(material, x, y, z, -1, -2, None, None);
+        editor.set_scaled_block(material, x, y, z, 0, -3, None, None);
+        editor.set_scaled_block(material, x, y, z, 0, 3, None, None);
+    }
 }
 
 /// Helper function to set blocks in a more scattered circular pattern.
-fn round3(editor: &mut WorldEditor, material: Block, x: i32, y: i32, z: i32) {
-    editor.set_block(material, x + 3, y, z - 1, None, None);
-    editor.set_block(material, x + 3, y, z + 1, None, None);
-    editor.set_block(material, x + 2, y, z - 2, None, None);
-    editor.set_block(material, x + 2, y, z + 2, None, None);
-    editor.set_block(material, x + 1, y, z - 3, None, None);
-    editor.set_block(material, x + 1, y, z + 3, None, None);
-    editor.set_block(material, x - 3, y, z - 1, None, None);
-    editor.set_block(material, x - 3, y, z + 1, None, None);
-    editor.set_block(material, x - 2, y, z - 2, None, None);
-    editor.set_block(material, x - 2, y, z + 2, None, None);
-    editor.set_block(material, x - 1, y, z + 3, None, None);
-    editor.set_block(material, x - 1, y, z - 3, None, None);
+fn round3(editor: &mut WorldEditor, material: Block, x: i32, layers: Range<i32>, z: i32) {
+    for y in layers {
+        editor.set_scaled_block(material, x, y, z, 3, -1, None, None);
+        editor.set_scaled_block(material, x, y, z, 3, 1, None, None);
+        editor.set_scaled_block(material, x, y, z, 2, -2, None, None);
+        editor.set_scaled_block(material, x, y, z, 2, 2, None, None);
+        editor.set_scaled_block(material, x, y, z, 1, -3, None, None);
+        editor.set_scaled_block(material, x, y, z, 1, 3, None, None);
+        editor.set_scaled_block(material, x, y, z, -3, -1, None, None);
+        editor.set_scaled_block(material, x, y, z, -3, 1, None, None);
+        editor.set_scaled_block(material, x, y, z, -2, -2, None, None);
+        editor.set_scaled_block(material, x, y, z, -2, 2, None, None);
+        editor.set_scaled_block(material, x, y, z, -1, 3, None, None);
+        editor.set_scaled_block(material, x, y, z, -1, -3, None, None);
+    }
+}
+
+/// Helper function to set a column of blocks at the given offset from the trunk.
+fn column(
+    editor: &mut WorldEditor,
+    material: Block,
+    x: i32,
+    z: i32,
+    dx: i32,
+    dz: i32,
+    layers: Range<i32>,
+) {
+    for y in layers {
+        editor.set_scaled_block(material, x, y, z, dx, dz, None, None);
+    }
 }
 
 /// Function to create different types of trees.
@@ -57,228 +79,169 @@ pub fn create_tree(editor: &mut WorldEditor, x: i32, y: i32, z: i32, typetree: u
         return;
     }
 
+    // Bottom of each layer of the tree at the vertical scale, by its height at a scale of 1
+    let levels: Vec<i32> = (0..=12)
+        .map(|layer: i32| y + editor.scale_height(layer))
+        .collect();
+
     match typetree {
         1 => {
             // Oak tree
-            editor.fill_blocks(Block::OakLog, x, y, z, x, y + 8, z, None, None);
-            editor.fill_blocks(
-                Block::OakLeaves,
-                x - 1,
-                y + 3,
-                z,
-                x - 1,
-                y + 9,
-                z,
-                None,
-                None,
-            );
-            editor.fill_blocks(
-                Block::OakLeaves,
-                x + 1,
-                y + 3,
-                z,
-                x + 1,
-                y + 9,
-                z,
-                None,
-                None,
-            );
-            editor.fill_blocks(
-                Block::OakLeaves,
-                x,
-                y + 3,
-                z - 1,
-                x,
-                y + 9,
-                z - 1,
-                None,
-                None,
-            );
-            editor.fill_blocks(
-                Block::OakLeaves,
-                x,
-                y + 3,
-                z + 1,
-                x,
-                y + 9,
-                z + 1,
-                None,
-                None,
-            );
-            editor.fill_blocks(Block::OakLeaves, x, y + 9, z, x, y + 10, z, None, None);
-            round1(editor, Block::OakLeaves, x, y + 8, z);
-            round1(editor, Block::OakLeaves, x, y + 7, z);
-            round1(editor, Block::OakLeaves, x, y + 6, z);
-            round1(editor, Block::OakLeaves, x, y + 5, z);
-            round1(editor, Block::OakLeaves, x, y + 4, z);
-            round1(editor, Block::OakLeaves, x, y + 3, z);
-            round2(editor, Block::OakLeaves, x, y + 7, z);
-            round2(editor, Block::OakLeaves, x, y + 6, z);
-            round2(editor, Block::OakLeaves, x, y + 5, z);
-            round2(editor, Block::OakLeaves, x, y + 4, z);
-            round3(editor, Block::OakLeaves, x, y + 6, z);
-            round3(editor, Block::OakLeaves, x, y + 5, z);
+            column(editor, Block::OakLog, x, z, 0, 0, levels[0]..levels[9]);
+            column(editor, Block::OakLeaves, x, z, -1, 0, levels[3]..levels[10]);
+            column(editor, Block::OakLeaves, x, z, 1, 0, levels[3]..levels[10]);
+            column(editor, Block::OakLeaves, x, z, 0, -1, levels[3]..levels[10]);
+            column(editor, Block::OakLeaves, x, z, 0, 1, levels[3]..levels[10]);
+            column(editor, Block::OakLeaves, x, z, 0, 0, levels[9]..levels[11]);
+            round1(editor, Block::OakLeaves, x, levels[8]..levels[9], z);
+            round1(editor, Block::OakLeaves, x, levels[7]..levels[8], z);
+            round1(editor, Block::OakLeaves, x, levels[6]..levels[7], z);
+            round1(editor, Block::OakLeaves, x, levels[5]..levels[6], z);
+            round1(editor, Block::OakLeaves, x, levels[4]..levels[5], z);
+            round1(editor, Block::OakLeaves, x, levels[3]..levels[4], z);
+            round2(editor, Block::OakLeaves, x, levels[7]..levels[8], z);
+            round2(editor, Block::OakLeaves, x, levels[6]..levels[7], z);
+            round2(editor, Block::OakLeaves, x, levels[5]..levels[6], z);
+            round2(editor, Block::OakLeaves, x, levels[4]..levels[5], z);
+            round3(editor, Block::OakLeaves, x, levels[6]..levels[7], z);
+            round3(editor, Block::OakLeaves, x, levels[5]..levels[6], z);
 
             if snow {
-                editor.set_block(Block::SnowLayer, x, y + 11, z, None, None);
-                editor.set_block(Block::SnowLayer, x + 1, y + 10, z, None, None);
-                editor.set_block(Block::SnowLayer, x - 1, y + 10, z, None, None);
-                editor.set_block(Block::SnowLayer, x, y + 10, z - 1, None, None);
-                editor.set_block(Block::SnowLayer, x, y + 10, z + 1, None, None);
-                round1(editor, Block::SnowLayer, x, y + 9, z);
-                round1(editor, Block::SnowLayer, x, y + 8, z);
-                round1(editor, Block::SnowLayer, x, y + 7, z);
-                round1(editor, Block::SnowLayer, x, y + 6, z);
-                round2(editor, Block::SnowLayer, x, y + 8, z);
-                round2(editor, Block::SnowLayer, x, y + 7, z);
-                round2(editor, Block::SnowLayer, x, y + 6, z);
-                round2(editor, Block::SnowLayer, x, y + 5, z);
-                round3(editor, Block::SnowLayer, x, y + 7, z);
-                round3(editor, Block::SnowLayer, x, y + 6, z);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[11], z, 0, 0, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[10], z, 1, 0, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[10], z, -1, 0, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[10], z, 0, -1, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[10], z, 0, 1, None, None);
+                round1(editor, Block::SnowLayer, x, levels[9]..levels[9] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[8]..levels[8] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[7]..levels[7] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[6]..levels[6] + 1, z);
+                round2(editor, Block::SnowLayer, x, levels[8]..levels[8] + 1, z);
+                round2(editor, Block::SnowLayer, x, levels[7]..levels[7] + 1, z);
+                round2(editor, Block::SnowLayer, x, levels[6]..levels[6] + 1, z);
+                round2(editor, Block::SnowLayer, x, levels[5]..levels[5] + 1, z);
+                round3(editor, Block::SnowLayer, x, levels[7]..levels[7] + 1, z);
+                round3(editor, Block::SnowLayer, x, levels[6]..levels[6] + 1, z);
             }
         }
         2 => {
             // Spruce tree
-            editor.fill_blocks(Block::SpruceLog, x, y, z, x, y + 9, z, None, None);
-            editor.fill_blocks(
+            column(editor, Block::SpruceLog, x, z, 0, 0, levels[0]..levels[10]);
+            column(
+                editor,
                 Block::BirchLeaves,
-                x - 1,
-                y + 3,
-                z,
-                x - 1,
-                y + 10,
+                x,
                 z,
-                None,
-                None,
+                -1,
+                0,
+                levels[3]..levels[11],
             );
-            editor.fill_blocks(
+            column(
+                editor,
                 Block::BirchLeaves,
-                x + 1,
-                y + 3,
-                z,
-                x + 1,
-                y + 10,
+                x,
                 z,
-                None,
-                None,
+                1,
+                0,
+                levels[3]..levels[11],
             );
-            editor.fill_blocks(
+            column(
+                editor,
                 Block::BirchLeaves,
                 x,
-                y + 3,
-                z - 1,
-                x,
-                y + 10,
-                z - 1,
-                None,
-                None,
+                z,
+                0,
+                -1,
+                levels[3]..levels[11],
             );
-            editor.fill_blocks(
+            column(
+                editor,
                 Block::BirchLeaves,
                 x,
-                y + 3,
-                z + 1,
+                z,
+                0,
+                1,
+                levels[3]..levels[11],
+            );
+            column(
+                editor,
+                Block::BirchLeaves,
                 x,
-                y + 10,
-                z + 1,
-                None,
-                None,
+                z,
+                0,
+                0,
+                levels[10]..levels[11],
             );
-            editor.set_block(Block::BirchLeaves, x, y + 10, z, None, None);
-            round1(editor, Block::BirchLeaves, x, y + 9, z);
-            round1(editor, Block::BirchLeaves, x, y + 7, z);
-            round1(editor, Block::BirchLeaves, x, y + 6, z);
-            round1(editor, Block::BirchLeaves, x, y + 4, z);
-            round1(editor, Block::BirchLeaves, x, y + 3, z);
-            round2(editor, Block::BirchLeaves, x, y + 6, z);
-            round2(editor, Block::BirchLeaves, x, y + 3, z);
+            round1(editor, Block::BirchLeaves, x, levels[9]..levels[10], z);
+            round1(editor, Block::BirchLeaves, x, levels[7]..levels[8], z);
+            round1(editor, Block::BirchLeaves, x, levels[6]..levels[7], z);
+            round1(editor, Block::BirchLeaves, x, levels[4]..levels[5], z);
+            round1(editor, Block::BirchLeaves, x, levels[3]..levels[4], z);
+            round2(editor, Block::BirchLeaves, x, levels[6]..levels[7], z);
+            round2(editor, Block::BirchLeaves, x, levels[3]..levels[4], z);
 
             if snow {
-                editor.set_block(Block::SnowLayer, x, y + 11, z, None, None);
-                editor.set_block(Block::SnowLayer, x + 1, y + 11, z, None, None);
-                editor.set_block(Block::SnowLayer, x - 1, y + 11, z, None, None);
-                editor.set_block(Block::SnowLayer, x, y + 11, z - 1, None, None);
-                editor.set_block(Block::SnowLayer, x, y + 11, z + 1, None, None);
-                round1(editor, Block::SnowLayer, x, y + 10, z);
-                round1(editor, Block::SnowLayer, x, y + 8, z);
-                round1(editor, Block::SnowLayer, x, y + 7, z);
-                round1(editor, Block::SnowLayer, x, y + 5, z);
-                round1(editor, Block::SnowLayer, x, y + 4, z);
-                round2(editor, Block::SnowLayer, x, y + 7, z);
-                round2(editor, Block::SnowLayer, x, y + 4, z);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[11], z, 0, 0, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[11], z, 1, 0, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[11], z, -1, 0, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[11], z, 0, -1, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[11], z, 0, 1, None, None);
+                round1(editor, Block::SnowLayer, x, levels[10]..levels[10] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[8]..levels[8] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[7]..levels[7] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[5]..levels[5] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[4]..levels[4] + 1, z);
+                round2(editor, Block::SnowLayer, x, levels[7]..levels[7] + 1, z);
+                round2(editor, Block::SnowLayer, x, levels[4]..levels[4] + 1, z);
             }
         }
         3 => {
             // Birch tree
-            editor.fill_blocks(Block::BirchLog, x, y, z, x, y + 6, z, None, None);
-            editor.fill_blocks(
-                Block::BirchLeaves,
-                x - 1,
-                y + 2,
-                z,
-                x - 1,
-                y + 7,
-                z,
-                None,
-                None,
-            );
-            editor.fill_blocks(
-                Block::BirchLeaves,
-                x + 1,
-                y + 2,
-                z,
-                x + 1,
-                y + 7,
-                z,
-                None,
-                None,
-            );
-            editor.fill_blocks(
+            column(editor, Block::BirchLog, x, z, 0, 0, levels[0]..levels[7]);
+            column(
+                editor,
                 Block::BirchLeaves,
                 x,
-                y + 2,
-                z - 1,
-                x,
-                y + 7,
-                z - 1,
-                None,
-                None,
+                z,
+                -1,
+                0,
+                levels[2]..levels[8],
             );
-            editor.fill_blocks(
+            column(editor, Block::BirchLeaves, x, z, 1, 0, levels[2]..levels[8]);
+            column(
+                editor,
                 Block::BirchLeaves,
                 x,
-                y + 2,
-                z + 1,
-                x,
-                y + 7,
-                z + 1,
-                None,
-                None,
+                z,
+                0,
+                -1,
+                levels[2]..levels[8],
             );
-            editor.fill_blocks(Block::BirchLeaves, x, y + 7, z, x, y + 8, z, None, None);
-            round1(editor, Block::BirchLeaves, x, y + 6, z);
-            round1(editor, Block::BirchLeaves, x, y + 5, z);
-            round1(editor, Block::BirchLeaves, x, y + 4, z);
-            round1(editor, Block::BirchLeaves, x, y + 3, z);
-            round1(editor, Block::BirchLeaves, x, y + 2, z);
-            round2(editor, Block::BirchLeaves, x, y + 2, z);
-            round2(editor, Block::BirchLeaves, x, y + 3, z);
-            round2(editor, Block::BirchLeaves, x, y + 4, z);
+            column(editor, Block::BirchLeaves, x, z, 0, 1, levels[2]..levels[8]);
+            column(editor, Block::BirchLeaves, x, z, 0, 0, levels[7]..levels[9]);
+            round1(editor, Block::BirchLeaves, x, levels[6]..levels[7], z);
+            round1(editor, Block::BirchLeaves, x, levels[5]..levels[6], z);
+            round1(editor, Block::BirchLeaves, x, levels[4]..levels[5], z);
+            round1(editor, Block::BirchLeaves, x, levels[3]..levels[4], z);
+            round1(editor, Block::BirchLeaves, x, levels[2]..levels[3], z);
+            round2(editor, Block::BirchLeaves, x, levels[2]..levels[3], z);
+            round2(editor, Block::BirchLeaves, x, levels[3]..levels[4], z);
+            round2(editor, Block::BirchLeaves, x, levels[4]..levels[5], z);
 
             if snow {
-                editor.set_block(Block::SnowLayer, x, y + 9, z, None, None);
-                editor.set_block(Block::SnowLayer, x + 1, y + 8, z, None, None);
-                editor.set_block(Block::SnowLayer, x - 1, y + 8, z, None, None);
-                editor.set_block(Block::SnowLayer, x, y + 8, z - 1, None, None);
-                editor.set_block(Block::SnowLayer, x, y + 8, z + 1, None, None);
-                round1(editor, Block::SnowLayer, x, y + 7, z);
-                round1(editor, Block::SnowLayer, x, y + 6, z);
-                round1(editor, Block::SnowLayer, x, y + 5, z);
-                round1(editor, Block::SnowLayer, x, y + 4, z);
-                round1(editor, Block::SnowLayer, x, y + 3, z);
-                round2(editor, Block::SnowLayer, x, y + 3, z);
-                round2(editor, Block::SnowLayer, x, y + 4, z);
-                round2(editor, Block::SnowLayer, x, y + 5, z);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[9], z, 0, 0, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[8], z, 1, 0, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[8], z, -1, 0, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[8], z, 0, -1, None, None);
+                editor.set_scaled_block(Block::SnowLayer, x, levels[8], z, 0, 1, None, None);
+                round1(editor, Block::SnowLayer, x, levels[7]..levels[7] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[6]..levels[6] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[5]..levels[5] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[4]..levels[4] + 1, z);
+                round1(editor, Block::SnowLayer, x, levels[3]..levels[3] + 1, z);
+                round2(editor, Block::SnowLayer, x, levels[3]..levels[3] + 1, z);
+                round2(editor, Block::SnowLayer, x, levels[4]..levels[4] + 1, z);
+                round2(editor, Block::SnowLayer, x, levels[5]..levels[5] + 1, z);
             }
         }
         _ => {} // Do nothing if typetree is not recognized
@@ -2,6 +2,7 @@ use geo::{Contains, Intersects, LineString, Point, Polygon, Rect};
 use std::time::Instant;
 
 use crate::{
+    biome_definitions::{water_biome, Biome},
     block_definitions::Block,
     osm_parser::{ProcessedMemberRole, ProcessedNode, ProcessedRelation},
     world_editor::WorldEditor,
@@ -21,6 +22,7 @@ pub fn generate_water_areas(
     // don't handle water below layer 0
     if let Some(layer) = element.tags.get("layer") {
         if layer.parse::<i32>().map(|x| x < 0).unwrap_or(false) {
+            editor.report_issue("negative layer");
             return;
         }
     }
@@ -37,14 +39,18 @@ pub fn generate_water_areas(
 
     merge_loopy_loops(&mut outers);
     if !verify_loopy_loops(&outers) {
+        editor.report_issue("outline not closed");
         return;
     }
 
     merge_loopy_loops(&mut inners);
     if !verify_loopy_loops(&inners) {
+        editor.report_issue("outline not closed");
         return;
     }
 
+    let biome: Biome = water_biome(&element.tags);
+
     let (max_x, max_z) = editor.get_max_coords();
     let outers: Vec<Vec<(f64, f64)>> = outers
         .iter()
@@ -70,6 +76,7 @@ pub fn generate_water_areas(
         inners,
         editor,
         ground_level,
+        biome,
         start_time,
     );
 }
@@ -161,6 +168,7 @@ fn verify_loopy_loops(loops: &[Vec<ProcessedNode>]) -> bool {
 // Water areas are absolutely huge. We can't easily flood fill the entire thing.
 // Instead, we'll iterate over all the blocks in our MC world, and check if each
 // one is in the river or not
+#[allow(clippy::too_many_arguments)]
 fn inverse_floodfill(
     max_x: i32,
     max_z: i32,
@@ -168,6 +176,7 @@ fn inverse_floodfill(
     inners: Vec<Vec<(f64, f64)>>,
     editor: &mut WorldEditor,
     ground_level: i32,
+    biome: Biome,
     start_time: Instant,
 ) {
     let min_x: i32 = 0;
@@ -187,6 +196,7 @@ fn inverse_floodfill(
         (min_x, min_z),
         (max_x, max_z),
         ground_level,
+        biome,
         &outers,
         &inners,
         editor,
@@ -194,10 +204,12 @@ fn inverse_floodfill(
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 fn inverse_floodfill_recursive(
     min: (i32, i32),
     max: (i32, i32),
     ground_level: i32,
+    biome: Biome,
     outers: &[Polygon],
     inners: &[Polygon],
     editor: &mut WorldEditor,
@@ -215,7 +227,7 @@ fn inverse_floodfill_recursive(
     }
 
     if (max.0 - min.0) * (max.1 - min.1) < ITERATIVE_THRES {
-        inverse_floodfill_iterative(min, max, ground_level, outers, inners, editor);
+        inverse_floodfill_iterative(min, max, ground_level, biome, outers, inners, editor);
 
         return;
     }
@@ -241,7 +253,7 @@ fn inverse_floodfill_recursive(
             // every block in rect is water
             // so we can safely just set the whole thing to water
 
-            rect_fill(min_x, max_x, min_z, max_z, ground_level, editor);
+            rect_fill(min_x, max_x, min_z, max_z, ground_level, biome, editor);
 
             continue;
         }
@@ -269,6 +281,7 @@ fn inverse_floodfill_recursive(
                 (min_x, min_z),
                 (max_x, max_z),
                 ground_level,
+                biome,
                 &outers_intersects,
                 &inners_intersects,
                 editor,
@@ -283,6 +296,7 @@ fn inverse_floodfill_iterative(
     min: (i32, i32),
     max: (i32, i32),
     ground_level: i32,
+    biome: Biome,
     outers: &[Polygon],
     inners: &[Polygon],
     editor: &mut WorldEditor,
@@ -295,6 +309,7 @@ fn inverse_floodfill_iterative(
                 && inners.iter().all(|poly: &Polygon| !poly.contains(&p))
             {
                 editor.set_block(Block::Water, x, ground_level, z, None, None);
+                editor.set_biome(biome, x, z);
             }
         }
     }
@@ -306,11 +321,13 @@ fn rect_fill(
     min_z: i32,
     max_z: i32,
     ground_level: i32,
+    biome: Biome,
     editor: &mut WorldEditor,
 ) {
     for x in min_x..max_x {
         for z in min_z..max_z {
             editor.set_block(Block::Water, x, ground_level, z, None, None);
+            editor.set_biome(biome, x, z);
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::biome_definitions::Biome;
 use crate::block_definitions::Block;
 use crate::bresenham::bresenham_line;
 use crate::osm_parser::ProcessedWay;
@@ -6,7 +7,11 @@ use crate::world_editor::WorldEditor;
 pub fn generate_waterways(editor: &mut WorldEditor, element: &ProcessedWay, ground_level: i32) {
     if let Some(_waterway_type) = element.tags.get("waterway") {
         let mut previous_node: Option<(i32, i32)> = None;
-        let mut waterway_width: i32 = 4; // Default waterway width
+        let mut waterway_width: i32 = editor.style().width("waterway", &element.tags).unwrap_or(4); // Default waterway width
+        let water_block: Block = editor
+            .style()
+            .block("waterway", &element.tags)
+            .unwrap_or(Block::Water);
 
         // Check for custom width in tags
         if let Some(width_str) = element.tags.get("width") {
@@ -17,6 +22,7 @@ pub fn generate_waterways(editor: &mut WorldEditor, element: &ProcessedWay, grou
                     .unwrap_or(waterway_width)
             });
         }
+        waterway_width = editor.scale_width(waterway_width);
 
         // Process nodes to create waterways
         for node in &element.nodes {
@@ -32,7 +38,8 @@ pub fn generate_waterways(editor: &mut WorldEditor, element: &ProcessedWay, grou
                     for (bx, _, bz) in bresenham_points {
                         for x in (bx - waterway_width / 2)..=(bx + waterway_width / 2) {
                             for z in (bz - waterway_width / 2)..=(bz + waterway_width / 2) {
-                                editor.set_block(Block::Water, x, ground_level, z, None, None); // Set water block
+                                editor.set_block(water_block, x, ground_level, z, None, None); // Set water block
+                                editor.set_biome(Biome::River, x, z);
                                 editor.set_block(
                                     Block::Air,
                                     x,
@@ -0,0 +1,57 @@
+use clap::ValueEnum;
+
+/// Game mode players start the world in.
+#[derive(ValueEnum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GameMode {
+    Survival,
+    Creative,
+    Adventure,
+    Spectator,
+}
+
+impl GameMode {
+    pub fn id(&self) -> i32 {
+        *self as i32
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Difficulty {
+    Peaceful,
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn id(&self) -> i32 {
+        *self as i32
+    }
+}
+
+/// Generator for the terrain outside the generated area.
+#[derive(ValueEnum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WorldPreset {
+    /// Flat ground at the height of the generated ground layer
+    Flat,
+    /// Nothing but air
+    Void,
+}
+
+/// Parses a time of day, either in ticks (0-23999) or by name.
+pub fn parse_time_of_day(arg: &str) -> Result<i64, String> {
+    match arg {
+        "day" => Ok(1000),
+        "noon" => Ok(6000),
+        "sunset" => Ok(12000),
+        "night" => Ok(13000),
+        "midnight" => Ok(18000),
+        ticks => match ticks.parse::<i64>() {
+            Ok(ticks) if (0..24000).contains(&ticks) => Ok(ticks),
+            _ => Err(format!(
+                "Invalid time of day: {} (expected 0-23999, day, noon, sunset, night or midnight)",
+                arg
+            )),
+        },
+    }
+}
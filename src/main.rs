@@ -1,30 +1,37 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod args;
+mod backup;
+mod biome_definitions;
 mod block_definitions;
 mod bresenham;
 mod colors;
 mod data_processing;
 mod element_processing;
 mod floodfill;
+mod level_settings;
+mod minecraft_version;
 mod osm_parser;
 mod progress;
+mod report;
 mod retrieve_data;
+mod session_lock;
+mod style;
 mod version_check;
 mod world_editor;
 
-use args::Args;
-use clap::Parser;
+use args::{Args, RestoreArgs};
+use clap::{Parser, ValueEnum};
 use colored::*;
-use fastnbt::Value;
-use flate2::read::GzDecoder;
-use fs2::FileExt;
+use level_settings::{Difficulty, GameMode, WorldPreset};
 use log::{error, LevelFilter};
+use minecraft_version::MinecraftVersion;
 use rfd::FileDialog;
+use session_lock::SessionLock;
 use std::{
     env,
     fs::{self, File},
-    io::{Read, Write},
+    io::Write,
     panic,
     path::{Path, PathBuf},
 };
@@ -57,6 +64,27 @@ fn main() {
     // Parse arguments to decide whether to launch the UI or CLI
     let raw_args: Vec<String> = std::env::args().collect();
 
+    // `arnis restore <world> <backup-id>` undoes a generation
+    if raw_args.get(1).map(|arg: &String| arg.as_str()) == Some("restore") {
+        let restore_args: RestoreArgs = RestoreArgs::parse_from(&raw_args[1..]);
+        let world_dir: &Path = Path::new(&restore_args.world);
+        let restored: Result<(), String> = SessionLock::acquire(world_dir)
+            .and_then(|_lock: SessionLock| backup::restore(world_dir, &restore_args.backup_id));
+        match restored {
+            Ok(()) => println!(
+                "{}",
+                format!("Restored the world from backup {}", restore_args.backup_id)
+                    .green()
+                    .bold()
+            ),
+            Err(e) => {
+                eprintln!("{}", format!("Error! {}", e).red().bold());
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     // Check if either `--help` or `--path` is present to run command-line mode
     let is_help: bool = raw_args.iter().any(|arg: &String| arg == "--help");
     let is_path_provided: bool = raw_args
@@ -76,7 +104,7 @@ fn main() {
         }
 
         // Parse input arguments
-        let args: Args = Args::parse();
+        let mut args: Args = Args::parse();
         args.run();
 
         let bbox: Vec<f64> = args
@@ -101,6 +129,23 @@ fn main() {
             osm_parser::get_priority(element)
         });
 
+        // Resolve the spawn now that the projection of the area is known
+        match osm_parser::resolve_spawn(
+            &parsed_elements,
+            bbox_tuple,
+            &args,
+            scale_factor_x,
+            scale_factor_z,
+        ) {
+            Ok(Some(spawn_point)) => args.spawn_point = Some(spawn_point),
+            Ok(None) => {}
+            Err(e) => eprintln!(
+                "{} {}, spawning at the center of the area",
+                "Warning:".yellow().bold(),
+                e
+            ),
+        }
+
         // Write the parsed OSM data to a file for inspection
         if args.debug {
             let mut output_file: File =
@@ -118,8 +163,12 @@ fn main() {
         }
 
         // Generate world
-        let _ =
-            data_processing::generate_world(parsed_elements, &args, scale_factor_x, scale_factor_z);
+        if let Err(e) =
+            data_processing::generate_world(parsed_elements, &args, scale_factor_x, scale_factor_z)
+        {
+            eprintln!("{}", format!("Error! {}", e).red().bold());
+            std::process::exit(1);
+        }
     } else {
         // Launch the UI
         println!("Launching UI...");
@@ -147,6 +196,7 @@ fn main() {
             .invoke_handler(tauri::generate_handler![
                 gui_select_world,
                 gui_start_generation,
+                gui_undo_generation,
                 gui_get_version,
                 gui_check_for_updates
             ])
@@ -205,18 +255,9 @@ fn gui_select_world(generate_new: bool) -> Result<String, i32> {
         if let Some(path) = dialog.pick_folder() {
             // Check if the "region" folder exists within the selected directory
             if path.join("region").exists() {
-                // Check the 'session.lock' file
-                let session_lock_path = path.join("session.lock");
-                if session_lock_path.exists() {
-                    // Try to acquire a lock on the session.lock file
-                    if let Ok(file) = File::open(&session_lock_path) {
-                        if file.try_lock_shared().is_err() {
-                            return Err(2); // Error code 2: The selected world is currently in use
-                        } else {
-                            // Release the lock immediately
-                            let _ = file.unlock();
-                        }
-                    }
+                // Check the 'session.lock' file without changing it
+                if SessionLock::is_locked(&path) {
+                    return Err(2); // Error code 2: The selected world is currently in use
                 }
 
                 return Ok(path.display().to_string());
@@ -249,57 +290,8 @@ fn create_new_world(base_path: &Path) -> Result<String, String> {
     fs::create_dir_all(new_world_path.join("region"))
         .map_err(|e| format!("Failed to create world directory: {}", e))?;
 
-    // Copy the region template file
-    const REGION_TEMPLATE: &[u8] = include_bytes!("../mcassets/region.template");
-    let region_path = new_world_path.join("region").join("r.0.0.mca");
-    fs::write(&region_path, REGION_TEMPLATE)
-        .map_err(|e| format!("Failed to create region file: {}", e))?;
-
     // Add the level.dat file
-    const LEVEL_TEMPLATE: &[u8] = include_bytes!("../mcassets/level.dat");
-
-    // Decompress the gzipped level.template
-    let mut decoder = GzDecoder::new(LEVEL_TEMPLATE);
-    let mut decompressed_data = Vec::new();
-    decoder
-        .read_to_end(&mut decompressed_data)
-        .map_err(|e| format!("Failed to decompress level.template: {}", e))?;
-
-    // Parse the decompressed NBT data
-    let mut level_data: Value = fastnbt::from_bytes(&decompressed_data)
-        .map_err(|e| format!("Failed to parse level.dat template: {}", e))?;
-
-    // Modify the LevelName and LastPlayed fields
-    if let Value::Compound(ref mut root) = level_data {
-        if let Some(Value::Compound(ref mut data)) = root.get_mut("Data") {
-            // Update LevelName
-            data.insert("LevelName".to_string(), Value::String(unique_name.clone()));
-
-            // Update LastPlayed to the current Unix time in milliseconds
-            let current_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map_err(|e| format!("Failed to get current time: {}", e))?;
-            let current_time_millis = current_time.as_millis() as i64;
-            data.insert("LastPlayed".to_string(), Value::Long(current_time_millis));
-        }
-    }
-
-    // Serialize the updated NBT data back to bytes
-    let serialized_level_data: Vec<u8> = fastnbt::to_bytes(&level_data)
-        .map_err(|e| format!("Failed to serialize updated level.dat: {}", e))?;
-
-    // Compress the serialized data back to gzip
-    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
-    encoder
-        .write_all(&serialized_level_data)
-        .map_err(|e| format!("Failed to compress updated level.dat: {}", e))?;
-    let compressed_level_data = encoder
-        .finish()
-        .map_err(|e| format!("Failed to finalize compression for level.dat: {}", e))?;
-
-    // Write the level.dat file
-    fs::write(new_world_path.join("level.dat"), compressed_level_data)
-        .map_err(|e| format!("Failed to create level.dat file: {}", e))?;
+    world_editor::level_dat::create(&new_world_path, &unique_name)?;
 
     // Add the icon.png file
     const ICON_TEMPLATE: &[u8] = include_bytes!("../mcassets/icon.png");
@@ -309,6 +301,20 @@ fn create_new_world(base_path: &Path) -> Result<String, String> {
     Ok(new_world_path.display().to_string())
 }
 
+/// Restores the world from the backup made by the last generation and removes that backup.
+#[tauri::command]
+fn gui_undo_generation(selected_world: String) -> Result<String, String> {
+    let world_dir: &Path = Path::new(&selected_world);
+    let backup_id: String = backup::list(world_dir)
+        .pop()
+        .ok_or_else(|| "No generation to undo".to_string())?;
+
+    let _lock: SessionLock = SessionLock::acquire(world_dir)?;
+    backup::restore(world_dir, &backup_id)?;
+    backup::remove(world_dir, &backup_id)?;
+    Ok(backup_id)
+}
+
 #[tauri::command]
 fn gui_get_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
@@ -323,6 +329,7 @@ fn gui_check_for_updates() -> Result<bool, String> {
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 fn gui_start_generation(
     bbox_text: String,
     selected_world: String,
@@ -330,7 +337,23 @@ fn gui_start_generation(
     ground_level: i32,
     winter_mode: bool,
     floodfill_timeout: u64,
+    game_mode: String,
+    difficulty: String,
+    time_of_day: String,
+    daylight_cycle: bool,
+    mob_spawning: bool,
+    weather_cycle: bool,
+    world_preset: String,
 ) -> Result<(), String> {
+    // Validating the level settings up front so the GUI gets the error
+    let gamemode: GameMode = GameMode::from_str(&game_mode, true)
+        .map_err(|e| format!("Invalid game mode {}: {}", game_mode, e))?;
+    let difficulty: Difficulty = Difficulty::from_str(&difficulty, true)
+        .map_err(|e| format!("Invalid difficulty {}: {}", difficulty, e))?;
+    let time: i64 = level_settings::parse_time_of_day(&time_of_day)?;
+    let world_preset: WorldPreset = WorldPreset::from_str(&world_preset, true)
+        .map_err(|e| format!("Invalid world preset {}: {}", world_preset, e))?;
+
     tauri::async_runtime::spawn(async move {
         if let Err(e) = tokio::task::spawn_blocking(move || {
             // Utility function to reorder bounding box coordinates
@@ -348,6 +371,15 @@ fn gui_start_generation(
                 return Err("Invalid bounding box format".to_string());
             }
 
+            let version: MinecraftVersion = MinecraftVersion::default();
+            version.check_ground_level(ground_level)?;
+
+            // The preview is shown in the GUI once the generation is done
+            let preview_path: String = Path::new(&selected_world)
+                .join("preview.png")
+                .display()
+                .to_string();
+
             // Create an Args instance with the chosen bounding box and world directory path
             let args: Args = Args {
                 bbox: Some(bbox_text),
@@ -355,10 +387,32 @@ fn gui_start_generation(
                 path: selected_world,
                 downloader: "requests".to_string(),
                 scale: world_scale,
+                vertical_scale: None,
+                style: None,
                 ground_level,
+                topsoil_depth: 1,
+                fill_underground: false,
                 winter: winter_mode,
+                bedrock: false,
+                mc_version: version,
+                spawn_point: None,
+                spawn: None,
+                spawn_name: None,
+                gamemode: Some(gamemode),
+                difficulty: Some(difficulty),
+                time: Some(time),
+                daylight_cycle: Some(daylight_cycle),
+                mob_spawning: Some(mob_spawning),
+                weather_cycle: Some(weather_cycle),
+                world_preset: Some(world_preset),
+                dry_run: false,
+                no_backup: false,
                 debug: false,
                 timeout: Some(std::time::Duration::from_secs(floodfill_timeout)),
+                export: vec![],
+                preview: Some(preview_path),
+                preview_shading: true,
+                report_json: None,
             };
 
             // Reorder bounding box coordinates for further processing
@@ -373,12 +427,15 @@ fn gui_start_generation(
                         osm_parser::get_priority(element)
                     });
 
-                    let _ = data_processing::generate_world(
+                    if let Err(e) = data_processing::generate_world(
                         parsed_elements,
                         &args,
                         scale_factor_x,
                         scale_factor_z,
-                    );
+                    ) {
+                        progress::emit_gui_error(&e);
+                        return Err(e);
+                    }
                     Ok(())
                 }
                 Err(e) => Err(format!("Failed to start generation: {}", e)),
@@ -0,0 +1,133 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A Minecraft Java Edition release the world can be written for.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct MinecraftVersion {
+    name: &'static str,
+    data_version: i32,
+    pack_format: i32,
+}
+
+/// Every supported release with its data version and data pack format, oldest first.
+/// New worlds start from the level.dat in mcassets, so a release is only supported once the
+/// level.dat written for it matches that release. The template is a 1.21.4 world.
+const SUPPORTED_VERSIONS: [MinecraftVersion; 1] = [MinecraftVersion::new("1.21.4", 4189, 61)];
+
+/// Lowest and highest y coordinate blocks can be placed at, the same in every supported release.
+/// The chunk layout of the world editor is built on them.
+pub const MIN_BUILD_HEIGHT: i32 = -64;
+pub const MAX_BUILD_HEIGHT: i32 = 319;
+
+// Data versions of the releases that changed something we write
+pub const V1_19: i32 = 3105;
+pub const V1_20: i32 = 3463;
+pub const V1_20_3: i32 = 3698;
+pub const V1_20_5: i32 = 3837;
+pub const V1_21: i32 = 3953;
+
+impl MinecraftVersion {
+    const fn new(name: &'static str, data_version: i32, pack_format: i32) -> Self {
+        Self {
+            name,
+            data_version,
+            pack_format,
+        }
+    }
+
+    /// Data version stored in chunks, level.dat and schematics.
+    pub fn data_version(&self) -> i32 {
+        self.data_version
+    }
+
+    /// Whether this release is the one with the given data version or newer.
+    pub fn at_least(&self, data_version: i32) -> bool {
+        self.data_version >= data_version
+    }
+
+    /// Signs have text on both sides, stored as `front_text` and `back_text`.
+    pub fn has_two_sided_signs(&self) -> bool {
+        self.at_least(V1_20)
+    }
+
+    /// Item stacks store `count` and data components instead of `Count` and `tag`.
+    pub fn has_item_components(&self) -> bool {
+        self.at_least(V1_20_5)
+    }
+
+    /// Data pack format understood by this release.
+    pub fn pack_format(&self) -> i32 {
+        self.pack_format
+    }
+
+    /// Data packs store functions in `function` instead of `functions` since 1.21.
+    pub fn function_directory(&self) -> &'static str {
+        if self.at_least(V1_21) {
+            "function"
+        } else {
+            "functions"
+        }
+    }
+
+    /// Lowest y coordinate a block can be placed at.
+    pub fn min_build_height(&self) -> i32 {
+        MIN_BUILD_HEIGHT
+    }
+
+    /// Highest y coordinate a block can be placed at.
+    pub fn max_build_height(&self) -> i32 {
+        MAX_BUILD_HEIGHT
+    }
+
+    /// Checks that the ground level leaves room for the surface below the build limit.
+    pub fn check_ground_level(&self, ground_level: i32) -> Result<(), String> {
+        if ground_level < self.min_build_height() || ground_level >= self.max_build_height() {
+            return Err(format!(
+                "Ground level must be between {} and {} in Minecraft {}",
+                self.min_build_height(),
+                self.max_build_height() - 1,
+                self
+            ));
+        }
+        Ok(())
+    }
+
+    fn supported_names() -> String {
+        SUPPORTED_VERSIONS
+            .iter()
+            .map(|version: &MinecraftVersion| version.name)
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+}
+
+impl Default for MinecraftVersion {
+    /// The newest supported release.
+    fn default() -> Self {
+        SUPPORTED_VERSIONS[SUPPORTED_VERSIONS.len() - 1]
+    }
+}
+
+impl FromStr for MinecraftVersion {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        SUPPORTED_VERSIONS
+            .iter()
+            .find(|version: &&MinecraftVersion| version.name == name.trim())
+            .copied()
+            .ok_or_else(|| {
+                format!(
+                    "Unsupported Minecraft version {} (supported: {})",
+                    name,
+                    Self::supported_names()
+                )
+            })
+    }
+}
+
+impl fmt::Display for MinecraftVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
@@ -102,6 +102,36 @@ impl ProcessedElement {
         }
     }
 
+    /// Average position of the nodes of the element, using the outer ways of relations.
+    pub fn center(&self) -> Option<(i32, i32)> {
+        let nodes: Vec<&ProcessedNode> = match self {
+            ProcessedElement::Relation(relation) => relation
+                .members
+                .iter()
+                .filter(|member: &&ProcessedMember| member.role == ProcessedMemberRole::Outer)
+                .flat_map(|member: &ProcessedMember| member.way.nodes.iter())
+                .collect(),
+            _ => self.nodes().collect(),
+        };
+        if nodes.is_empty() {
+            return None;
+        }
+
+        let count: i64 = nodes.len() as i64;
+        let x: i64 = nodes
+            .iter()
+            .map(|node: &&ProcessedNode| i64::from(node.x))
+            .sum::<i64>()
+            / count;
+        let z: i64 = nodes
+            .iter()
+            .map(|node: &&ProcessedNode| i64::from(node.z))
+            .sum::<i64>()
+            / count;
+
+        Some((x as i32, z as i32))
+    }
+
     pub fn nodes<'a>(&'a self) -> Box<dyn Iterator<Item = &'a ProcessedNode> + 'a> {
         match self {
             ProcessedElement::Node(node) => Box::new([node].into_iter()),
@@ -264,6 +294,54 @@ pub fn parse_osm_data(
     (processed_elements, scale_factor_x, scale_factor_z)
 }
 
+/// Resolves the spawn given as a coordinate or as the name of a feature to block coordinates.
+/// Returns `None` if neither was given.
+pub fn resolve_spawn(
+    elements: &[ProcessedElement],
+    bbox: (f64, f64, f64, f64),
+    args: &Args,
+    scale_factor_x: f64,
+    scale_factor_z: f64,
+) -> Result<Option<(i32, i32)>, String> {
+    if let Some((lat, lon)) = args.spawn {
+        return Ok(Some(lat_lon_to_minecraft_coords(
+            lat,
+            lon,
+            bbox,
+            scale_factor_z,
+            scale_factor_x,
+        )));
+    }
+
+    let Some(name) = &args.spawn_name else {
+        return Ok(None);
+    };
+    let search: String = name.trim().to_lowercase();
+    let feature_name = |element: &ProcessedElement| -> Option<String> {
+        element
+            .tags()
+            .get("name")
+            .map(|feature_name: &String| feature_name.to_lowercase())
+    };
+
+    // Prefer an exact match, then any feature containing the name
+    let element: &ProcessedElement = elements
+        .iter()
+        .find(|element: &&ProcessedElement| feature_name(element).as_ref() == Some(&search))
+        .or_else(|| {
+            elements.iter().find(|element: &&ProcessedElement| {
+                feature_name(element)
+                    .is_some_and(|feature_name: String| feature_name.contains(&search))
+            })
+        })
+        .ok_or_else(|| format!("No feature named \"{}\" found in the area", name))?;
+
+    element
+        .center()
+        .map(Some)
+        .ok_or_else(|| format!("The feature named \"{}\" has no position", name))
+}
+
 const PRIORITY_ORDER: [&str; 6] = [
     "entrance", "building", "highway", "waterway", "water", "barrier",
 ];
@@ -41,6 +41,15 @@ pub fn emit_gui_progress_update(progress: f64, message: &str) {
     }
 }
 
+/// Sends the rendered preview of the generated world to the UI as PNG data.
+pub fn emit_gui_preview(png_data: &[u8]) {
+    if let Some(window) = get_main_window() {
+        if let Err(e) = window.emit("preview-ready", png_data) {
+            eprintln!("Failed to emit preview event: {}", e);
+        }
+    }
+}
+
 pub fn emit_gui_error(message: &str) {
     emit_gui_progress_update(0.0, &format!("Error! {}", message));
 }
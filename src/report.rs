@@ -0,0 +1,140 @@
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+/// Tags that describe an element but do not decide how it looks, left out of the unhandled tags.
+const DESCRIPTIVE_KEYS: [&str; 12] = [
+    "name",
+    "addr:",
+    "source",
+    "ref",
+    "note",
+    "description",
+    "wikidata",
+    "wikipedia",
+    "website",
+    "phone",
+    "opening_hours",
+    "check_date",
+];
+
+/// Rows of each section printed to the console, the JSON report has all of them.
+const CONSOLE_ROWS: usize = 10;
+
+/// How the elements of the area were turned into blocks, to find the gaps in the mapping.
+#[derive(Default, Serialize)]
+pub struct GenerationReport {
+    /// Generated elements by the tag that decided how, e.g. "building=yes"
+    handled: BTreeMap<String, usize>,
+    /// Tags of the elements no generator handles
+    unhandled: BTreeMap<String, usize>,
+    /// Generated elements that were left out or fell back to defaults, by tag and reason
+    issues: BTreeMap<String, BTreeMap<String, usize>>,
+    /// Issues of the element being generated, assigned to its tag once it is done
+    #[serde(skip)]
+    pending_issues: Vec<String>,
+}
+
+impl GenerationReport {
+    /// Records an issue with the element being generated, once per element.
+    pub fn issue(&mut self, reason: &str) {
+        if !self
+            .pending_issues
+            .iter()
+            .any(|pending: &String| pending == reason)
+        {
+            self.pending_issues.push(reason.to_string());
+        }
+    }
+
+    /// Records an element that was generated based on the tag with the given key.
+    pub fn handled(&mut self, tags: &HashMap<String, String>, key: &str) {
+        let tag: String = format!(
+            "{}={}",
+            key,
+            tags.get(key)
+                .map(|value: &String| value.as_str())
+                .unwrap_or("")
+        );
+
+        for reason in self.pending_issues.drain(..) {
+            *self
+                .issues
+                .entry(tag.clone())
+                .or_default()
+                .entry(reason)
+                .or_default() += 1;
+        }
+        *self.handled.entry(tag).or_default() += 1;
+    }
+
+    /// Records an element that no generator handles.
+    pub fn unhandled(&mut self, tags: &HashMap<String, String>) {
+        self.pending_issues.clear();
+
+        for (key, value) in tags {
+            if DESCRIPTIVE_KEYS
+                .iter()
+                .any(|descriptive: &&str| key.starts_with(descriptive))
+            {
+                continue;
+            }
+            *self
+                .unhandled
+                .entry(format!("{}={}", key, value))
+                .or_default() += 1;
+        }
+    }
+
+    /// Prints the most common handled and unhandled tags and issues as tables.
+    pub fn print(&self) {
+        let handled_total: usize = self.handled.values().sum();
+        println!("{}", "Generation report:".bold());
+
+        print_table(
+            &format!("Handled elements ({})", handled_total),
+            self.handled
+                .iter()
+                .map(|(tag, count)| (tag.clone(), *count)),
+        );
+        print_table(
+            "Unhandled tags",
+            self.unhandled
+                .iter()
+                .map(|(tag, count)| (tag.clone(), *count)),
+        );
+        print_table(
+            "Skipped or defaulted",
+            self.issues.iter().flat_map(|(tag, reasons)| {
+                reasons
+                    .iter()
+                    .map(move |(reason, count)| (format!("{} ({})", tag, reason), *count))
+            }),
+        );
+    }
+
+    /// Writes the full report as JSON.
+    pub fn write_json(&self, path: &str) -> Result<(), String> {
+        let json: String = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize the generation report: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path, e))
+    }
+}
+
+/// Prints the rows with the highest counts and how many more there are.
+fn print_table(title: &str, rows: impl Iterator<Item = (String, usize)>) {
+    let mut rows: Vec<(String, usize)> = rows.collect();
+    if rows.is_empty() {
+        return;
+    }
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    println!("  {}:", title);
+    for (label, count) in rows.iter().take(CONSOLE_ROWS) {
+        println!("    {:>7}  {}", count, label);
+    }
+    if rows.len() > CONSOLE_ROWS {
+        println!("             ... and {} more", rows.len() - CONSOLE_ROWS);
+    }
+}
@@ -0,0 +1,102 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Exclusive lock on the `session.lock` of a world. Minecraft and servers hold the same
+/// lock while the world is open, so the world is never written by both at once.
+/// The lock is released when this is dropped.
+pub struct SessionLock {
+    file: File,
+}
+
+impl SessionLock {
+    /// Locks the world, failing if it is open in Minecraft or locked by another run.
+    pub fn acquire(world_dir: &Path) -> Result<Self, String> {
+        let mut file: File = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(world_dir.join("session.lock"))
+            .map_err(|e| format!("Failed to open session.lock: {}", e))?;
+
+        if try_lock(&file).is_err() {
+            return Err(
+                "The world is in use by Minecraft or another program, close it and try again"
+                    .to_string(),
+            );
+        }
+
+        // Minecraft writes a snowman into the lock file when it takes the lock
+        file.set_len(0)
+            .and_then(|_| file.write_all("☃".as_bytes()))
+            .map_err(|e| format!("Failed to write session.lock: {}", e))?;
+
+        Ok(Self { file })
+    }
+
+    /// Whether the world is open in Minecraft or locked by another run. Takes the lock and
+    /// releases it right away, leaving the contents of `session.lock` as they are.
+    pub fn is_locked(world_dir: &Path) -> bool {
+        let lock_path: PathBuf = world_dir.join("session.lock");
+        if !lock_path.exists() {
+            return false;
+        }
+
+        // Record locks need the file open for writing, even though nothing is written
+        match OpenOptions::new().read(true).write(true).open(&lock_path) {
+            Ok(file) => match try_lock(&file) {
+                Ok(()) => {
+                    let _ = unlock(&file);
+                    false
+                }
+                Err(_) => true,
+            },
+            Err(_) => true,
+        }
+    }
+}
+
+impl Drop for SessionLock {
+    fn drop(&mut self) {
+        let _ = unlock(&self.file);
+    }
+}
+
+/// Takes a POSIX record lock on the whole file, the kind Java's `FileChannel.tryLock`
+/// uses on Linux and macOS. `flock` locks do not conflict with those.
+#[cfg(unix)]
+fn try_lock(file: &File) -> io::Result<()> {
+    set_record_lock(file, libc::F_WRLCK)
+}
+
+#[cfg(unix)]
+fn unlock(file: &File) -> io::Result<()> {
+    set_record_lock(file, libc::F_UNLCK)
+}
+
+#[cfg(unix)]
+fn set_record_lock(file: &File, lock_type: libc::c_int) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    // A start and length of 0 cover the whole file, however large it grows
+    let mut lock: libc::flock = unsafe { std::mem::zeroed() };
+    lock.l_type = lock_type as libc::c_short;
+    lock.l_whence = libc::SEEK_SET as libc::c_short;
+
+    if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_SETLK, &lock) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// On Windows Java locks with `LockFileEx`, which is what fs2 uses as well.
+#[cfg(not(unix))]
+fn try_lock(file: &File) -> io::Result<()> {
+    fs2::FileExt::try_lock_exclusive(file)
+}
+
+#[cfg(not(unix))]
+fn unlock(file: &File) -> io::Result<()> {
+    fs2::FileExt::unlock(file)
+}
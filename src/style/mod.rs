@@ -0,0 +1,151 @@
+use crate::args::Args;
+use crate::block_definitions::Block;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Style shipped with Arnis, the one used without --style.
+const DEFAULT_STYLE: &str = include_str!("default.toml");
+
+/// A style file as written, with blocks still given by name.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct StyleFile {
+    #[serde(default)]
+    winter: BTreeMap<String, String>,
+    #[serde(default)]
+    rule: Vec<RuleFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    tags: BTreeMap<String, String>,
+    block: Option<String>,
+    width: Option<i32>,
+    height: Option<i32>,
+    markings: Option<bool>,
+}
+
+/// How elements with certain tags are generated.
+struct StyleRule {
+    /// Tags an element needs to have, a value of "*" matches any value
+    tags: Vec<(String, String)>,
+    block: Option<Block>,
+    width: Option<i32>,
+    height: Option<i32>,
+    markings: Option<bool>,
+}
+
+impl StyleRule {
+    /// Whether the rule is about the given key and the element has all of its tags.
+    fn matches(&self, key: &str, tags: &HashMap<String, String>) -> bool {
+        self.tags.iter().any(|(rule_key, _)| rule_key == key)
+            && self.tags.iter().all(|(rule_key, rule_value)| {
+                tags.get(rule_key)
+                    .is_some_and(|value: &String| rule_value == "*" || rule_value == value)
+            })
+    }
+}
+
+/// Blocks, widths and heights of generated elements by their tags, from the built-in
+/// style and optionally a --style file that overrides it.
+pub struct Style {
+    rules: Vec<StyleRule>,
+    /// Blocks used in place of others, filled in winter mode only
+    winter: HashMap<Block, Block>,
+}
+
+impl Style {
+    /// Loads the built-in style and, if given, the --style file in TOML or JSON.
+    pub fn load(args: &Args) -> Result<Self, String> {
+        let default_style: StyleFile = toml::from_str(DEFAULT_STYLE)
+            .map_err(|e| format!("Failed to parse the built-in style: {}", e))?;
+        let custom_style: StyleFile = match &args.style {
+            Some(path) => read_style_file(Path::new(path))?,
+            None => StyleFile::default(),
+        };
+
+        let mut rules: Vec<StyleRule> = vec![];
+        for rule in custom_style.rule.into_iter().chain(default_style.rule) {
+            rules.push(StyleRule {
+                tags: rule.tags.into_iter().collect(),
+                block: rule.block.as_deref().map(parse_block).transpose()?,
+                width: rule.width,
+                height: rule.height,
+                markings: rule.markings,
+            });
+        }
+
+        let mut winter: HashMap<Block, Block> = HashMap::new();
+        if args.winter {
+            // Replacements of the --style file take precedence
+            for (from, to) in default_style.winter.iter().chain(&custom_style.winter) {
+                winter.insert(parse_block(from)?, parse_block(to)?);
+            }
+        }
+
+        Ok(Self { rules, winter })
+    }
+
+    /// Block of an element generated for the tag with the given key.
+    pub fn block(&self, key: &str, tags: &HashMap<String, String>) -> Option<Block> {
+        self.find(key, tags, |rule: &StyleRule| rule.block)
+            .map(|block: Block| self.seasonal(block))
+    }
+
+    /// Width in blocks at a scale of 1 of an element generated for the tag with the given key.
+    pub fn width(&self, key: &str, tags: &HashMap<String, String>) -> Option<i32> {
+        self.find(key, tags, |rule: &StyleRule| rule.width)
+    }
+
+    /// Height in blocks at a vertical scale of 1 of an element generated for the tag with
+    /// the given key.
+    pub fn height(&self, key: &str, tags: &HashMap<String, String>) -> Option<i32> {
+        self.find(key, tags, |rule: &StyleRule| rule.height)
+    }
+
+    /// Whether a road generated for the tag with the given key gets a center line.
+    pub fn markings(&self, key: &str, tags: &HashMap<String, String>) -> Option<bool> {
+        self.find(key, tags, |rule: &StyleRule| rule.markings)
+    }
+
+    /// The block to use in place of the given one in the current season.
+    pub fn seasonal(&self, block: Block) -> Block {
+        self.winter.get(&block).copied().unwrap_or(block)
+    }
+
+    /// The property from the first matching rule that sets it.
+    fn find<T>(
+        &self,
+        key: &str,
+        tags: &HashMap<String, String>,
+        property: impl Fn(&StyleRule) -> Option<T>,
+    ) -> Option<T> {
+        self.rules
+            .iter()
+            .filter(|rule: &&StyleRule| rule.matches(key, tags))
+            .find_map(property)
+    }
+}
+
+fn read_style_file(path: &Path) -> Result<StyleFile, String> {
+    let content: String = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read style {}: {}", path.display(), e))?;
+
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse style {}: {}", path.display(), e))
+    } else {
+        toml::from_str(&content)
+            .map_err(|e| format!("Failed to parse style {}: {}", path.display(), e))
+    }
+}
+
+fn parse_block(name: &str) -> Result<Block, String> {
+    Block::from_name(name).ok_or_else(|| format!("Unknown block in style: {}", name))
+}
@@ -0,0 +1,285 @@
+use crate::biome_definitions::Biome;
+use crate::block_definitions::Block;
+use fastnbt::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Block palette version written with every state, Bedrock 1.21.40.
+const BLOCK_VERSION: i32 = 18_163_712;
+
+/// Bedrock block state of a block, built from its Java name and state properties.
+/// Names and states follow Bedrock 1.21.40, where most blocks share their Java name.
+pub fn block_state(block: Block, properties: &BTreeMap<String, String>) -> Value {
+    let property = |key: &str| properties.get(key).map(|value: &String| value.as_str());
+
+    let (name, states): (&str, Vec<(&str, Value)>) = match block {
+        Block::Grass => ("short_grass", vec![]),
+        Block::Terracotta => ("hardened_clay", vec![]),
+        Block::EndStoneBricks => ("end_bricks", vec![]),
+        Block::NetherBricks => ("nether_brick", vec![]),
+        Block::RedNetherBricks => ("red_nether_brick", vec![]),
+        Block::Brick => ("brick_block", vec![]),
+        Block::SnowBlock => ("snow", vec![]),
+        Block::SnowLayer => (
+            "snow_layer",
+            vec![("covered_bit", Value::Byte(0)), ("height", Value::Int(0))],
+        ),
+        Block::Water => ("water", vec![("liquid_depth", Value::Int(0))]),
+        Block::StoneBlockSlab => ("smooth_stone_slab", slab_states()),
+        Block::StoneBrickSlab => ("stone_brick_slab", slab_states()),
+        Block::OakSlab => ("oak_slab", slab_states()),
+        Block::OakStairs | Block::StoneBrickStairs => (
+            block.name(),
+            vec![
+                ("upside_down_bit", Value::Byte(0)),
+                (
+                    "weirdo_direction",
+                    Value::Int(match property("facing") {
+                        Some("west") => 1,
+                        Some("south") => 2,
+                        Some("north") => 3,
+                        _ => 0,
+                    }),
+                ),
+            ],
+        ),
+        Block::OakLog
+        | Block::BirchLog
+        | Block::SpruceLog
+        | Block::PolishedBasalt
+        | Block::PurpurPillar
+        | Block::Deepslate => (block.name(), vec![("pillar_axis", string("y"))]),
+        Block::HayBale => (
+            block.name(),
+            vec![("deprecated", Value::Int(0)), ("pillar_axis", string("y"))],
+        ),
+        Block::OakLeaves | Block::BirchLeaves => (
+            block.name(),
+            vec![
+                ("persistent_bit", Value::Byte(1)),
+                ("update_bit", Value::Byte(0)),
+            ],
+        ),
+        Block::Farmland => (block.name(), vec![("moisturized_amount", Value::Int(7))]),
+        Block::Carrots | Block::Potatoes | Block::Wheat => {
+            (block.name(), vec![("growth", Value::Int(7))])
+        }
+        Block::Rail => (
+            "rail",
+            vec![(
+                "rail_direction",
+                Value::Int(match property("shape") {
+                    Some("east_west") => 1,
+                    Some("ascending_east") => 2,
+                    Some("ascending_west") => 3,
+                    Some("ascending_north") => 4,
+                    Some("ascending_south") => 5,
+                    Some("south_east") => 6,
+                    Some("south_west") => 7,
+                    Some("north_west") => 8,
+                    Some("north_east") => 9,
+                    _ => 0,
+                }),
+            )],
+        ),
+        Block::DarkOakDoorLower | Block::DarkOakDoorUpper => (
+            "dark_oak_door",
+            vec![
+                (
+                    "minecraft:cardinal_direction",
+                    string(property("facing").unwrap_or("north")),
+                ),
+                (
+                    "door_hinge_bit",
+                    Value::Byte(i8::from(property("hinge") == Some("right"))),
+                ),
+                ("open_bit", Value::Byte(0)),
+                (
+                    "upper_block_bit",
+                    Value::Byte(i8::from(block == Block::DarkOakDoorUpper)),
+                ),
+            ],
+        ),
+        Block::CobblestoneWall => {
+            let mut states: Vec<(&str, Value)> = ["east", "north", "south", "west"]
+                .into_iter()
+                .map(|direction: &str| {
+                    let connection: &str = match property(direction) {
+                        Some("low") => "short",
+                        Some("tall") => "tall",
+                        _ => "none",
+                    };
+                    (wall_connection_state(direction), string(connection))
+                })
+                .collect();
+            states.push((
+                "wall_post_bit",
+                Value::Byte(i8::from(property("up") != Some("false"))),
+            ));
+            ("cobblestone_wall", states)
+        }
+        Block::Ladder => ("ladder", vec![("facing_direction", Value::Int(3))]),
+        Block::Scaffolding => (
+            "scaffolding",
+            vec![
+                ("stability", Value::Int(0)),
+                ("stability_check", Value::Byte(0)),
+            ],
+        ),
+        Block::Cauldron => (
+            "cauldron",
+            vec![
+                ("cauldron_liquid", string("water")),
+                ("fill_level", Value::Int(0)),
+            ],
+        ),
+        Block::Sign => ("standing_sign", vec![ground_sign_direction(properties)]),
+        Block::OakWallSign => ("wall_sign", vec![facing_direction(properties)]),
+        Block::OakHangingSign | Block::OakWallHangingSign => (
+            "oak_hanging_sign",
+            vec![
+                ("attached_bit", Value::Byte(0)),
+                facing_direction(properties),
+                ground_sign_direction(properties),
+                (
+                    "hanging",
+                    Value::Byte(i8::from(block == Block::OakHangingSign)),
+                ),
+            ],
+        ),
+        Block::WhiteBanner => ("standing_banner", vec![ground_sign_direction(properties)]),
+        Block::Chest => (
+            "chest",
+            vec![("minecraft:cardinal_direction", string("north"))],
+        ),
+        Block::Barrel => (
+            "barrel",
+            vec![
+                ("facing_direction", Value::Int(1)),
+                ("open_bit", Value::Byte(0)),
+            ],
+        ),
+        Block::Lectern => (
+            "lectern",
+            vec![
+                ("minecraft:cardinal_direction", string("north")),
+                ("powered_bit", Value::Byte(0)),
+            ],
+        ),
+        // States of custom blocks are passed on as given, Bedrock servers with the
+        // same mod are expected to know them
+        Block::Custom(_) => (
+            block.name(),
+            properties
+                .iter()
+                .map(|(key, value)| (key.as_str(), string(value)))
+                .collect(),
+        ),
+        _ => (block.name(), vec![]),
+    };
+
+    let mut state: HashMap<String, Value> = HashMap::new();
+    state.insert(
+        "name".to_string(),
+        string(&format!("{}:{}", block.namespace(), name)),
+    );
+    state.insert(
+        "states".to_string(),
+        Value::Compound(
+            states
+                .into_iter()
+                .map(|(key, value): (&str, Value)| (key.to_string(), value))
+                .collect(),
+        ),
+    );
+    state.insert("version".to_string(), Value::Int(BLOCK_VERSION));
+    Value::Compound(state)
+}
+
+/// Bedrock name of a block, e.g. "minecraft:snow" for a snow block.
+pub fn block_name(block: Block) -> String {
+    match block_state(block, &BTreeMap::new()) {
+        Value::Compound(state) => match state.get("name") {
+            Some(Value::String(name)) => name.clone(),
+            _ => format!("{}:{}", block.namespace(), block.name()),
+        },
+        _ => format!("{}:{}", block.namespace(), block.name()),
+    }
+}
+
+/// The item frame block that holds an item frame placed as an entity on Java Edition.
+pub fn item_frame_state(glowing: bool, facing: i8) -> Value {
+    let name: &str = if glowing {
+        "minecraft:glow_frame"
+    } else {
+        "minecraft:frame"
+    };
+
+    let mut states: HashMap<String, Value> = HashMap::new();
+    states.insert(
+        "facing_direction".to_string(),
+        Value::Int(i32::from(facing)),
+    );
+    states.insert("item_frame_map_bit".to_string(), Value::Byte(0));
+    states.insert("item_frame_photo_bit".to_string(), Value::Byte(0));
+
+    let mut state: HashMap<String, Value> = HashMap::new();
+    state.insert("name".to_string(), string(name));
+    state.insert("states".to_string(), Value::Compound(states));
+    state.insert("version".to_string(), Value::Int(BLOCK_VERSION));
+    Value::Compound(state)
+}
+
+/// Numeric Bedrock id of a biome.
+pub fn biome_id(biome: Biome) -> i32 {
+    match biome {
+        Biome::Ocean => 0,
+        Biome::Plains => 1,
+        Biome::Forest => 4,
+        Biome::Swamp => 6,
+        Biome::River => 7,
+        Biome::FrozenOcean => 10,
+        Biome::FrozenRiver => 11,
+        Biome::SnowyPlains => 12,
+        Biome::Beach => 16,
+        Biome::SnowyBeach => 26,
+        Biome::SnowyTaiga => 30,
+    }
+}
+
+fn string(value: &str) -> Value {
+    Value::String(value.to_string())
+}
+
+fn slab_states() -> Vec<(&'static str, Value)> {
+    vec![("minecraft:vertical_half", string("bottom"))]
+}
+
+fn wall_connection_state(direction: &str) -> &'static str {
+    match direction {
+        "east" => "wall_connection_type_east",
+        "north" => "wall_connection_type_north",
+        "south" => "wall_connection_type_south",
+        _ => "wall_connection_type_west",
+    }
+}
+
+fn ground_sign_direction(properties: &BTreeMap<String, String>) -> (&'static str, Value) {
+    let rotation: i32 = properties
+        .get("rotation")
+        .and_then(|rotation: &String| rotation.parse().ok())
+        .unwrap_or(0);
+    ("ground_sign_direction", Value::Int(rotation))
+}
+
+fn facing_direction(properties: &BTreeMap<String, String>) -> (&'static str, Value) {
+    let facing: i32 = match properties
+        .get("facing")
+        .map(|facing: &String| facing.as_str())
+    {
+        Some("south") => 3,
+        Some("west") => 4,
+        Some("east") => 5,
+        _ => 2,
+    };
+    ("facing_direction", Value::Int(facing))
+}
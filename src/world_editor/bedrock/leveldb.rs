@@ -0,0 +1,468 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Number of entries between two restart points of a block.
+const RESTART_INTERVAL: usize = 16;
+/// Size at which a data block is closed and a new one started.
+const BLOCK_SIZE: usize = 4096;
+/// Magic number at the end of every table file.
+const TABLE_MAGIC: u64 = 0xdb4775248b80fb57;
+/// Size of the blocks of the log format used by the manifest.
+const LOG_BLOCK_SIZE: usize = 32768;
+
+const MANIFEST_NUMBER: u64 = 1;
+const TABLE_NUMBER: u64 = 2;
+const LOG_NUMBER: u64 = 3;
+
+/// Writes a fresh LevelDB database, as used by Bedrock Edition worlds, in one go.
+///
+/// All entries are collected in memory and written as a single sorted table that
+/// the manifest registers directly. Blocks are stored uncompressed, which every
+/// LevelDB implementation can read; the game compresses them on its next compaction.
+#[derive(Default)]
+pub struct LevelDbWriter {
+    entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl LevelDbWriter {
+    pub fn put(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        self.entries.insert(key, value);
+    }
+
+    /// Writes the database files into the given directory, which must not hold a database yet.
+    pub fn write(&self, dir: &Path) -> io::Result<()> {
+        if dir.join("CURRENT").exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "a database already exists",
+            ));
+        }
+        fs::create_dir_all(dir)?;
+
+        let table_path = dir.join(format!("{:06}.ldb", TABLE_NUMBER));
+        let (table_size, smallest, largest) = self.write_table(&table_path)?;
+
+        // Version edit describing the database state
+        let mut edit: Vec<u8> = vec![];
+        put_varint(&mut edit, 1); // Comparator
+        put_length_prefixed(&mut edit, b"leveldb.BytewiseComparator");
+        put_varint(&mut edit, 2); // Log number
+        put_varint(&mut edit, LOG_NUMBER);
+        put_varint(&mut edit, 9); // Previous log number
+        put_varint(&mut edit, 0);
+        put_varint(&mut edit, 3); // Next file number
+        put_varint(&mut edit, LOG_NUMBER + 1);
+        put_varint(&mut edit, 4); // Last sequence
+        put_varint(&mut edit, self.entries.len() as u64);
+        if table_size > 0 {
+            put_varint(&mut edit, 7); // New file
+            put_varint(&mut edit, 0); // Level
+            put_varint(&mut edit, TABLE_NUMBER);
+            put_varint(&mut edit, table_size);
+            put_length_prefixed(&mut edit, &smallest);
+            put_length_prefixed(&mut edit, &largest);
+        }
+
+        let manifest_name: String = format!("MANIFEST-{:06}", MANIFEST_NUMBER);
+        fs::write(dir.join(&manifest_name), log_record(&edit))?;
+        fs::write(dir.join("CURRENT"), format!("{}\n", manifest_name))?;
+        File::create(dir.join(format!("{:06}.log", LOG_NUMBER)))?;
+
+        if table_size == 0 {
+            fs::remove_file(table_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes all entries as a table. Returns its size and the smallest and largest internal key.
+    fn write_table(&self, path: &Path) -> io::Result<(u64, Vec<u8>, Vec<u8>)> {
+        let mut writer: TableWriter = TableWriter::new(BufWriter::new(File::create(path)?));
+
+        let mut smallest: Vec<u8> = vec![];
+        let mut largest: Vec<u8> = vec![];
+        for (sequence, (key, value)) in self.entries.iter().enumerate() {
+            let internal_key: Vec<u8> = internal_key(key, sequence as u64 + 1);
+            if smallest.is_empty() {
+                smallest = internal_key.clone();
+            }
+            writer.add(&internal_key, value)?;
+            largest = internal_key;
+        }
+
+        if self.entries.is_empty() {
+            return Ok((0, smallest, largest));
+        }
+
+        Ok((writer.finish()?, smallest, largest))
+    }
+}
+
+/// User key followed by the sequence number and the value type (1 = value).
+fn internal_key(key: &[u8], sequence: u64) -> Vec<u8> {
+    let mut internal_key: Vec<u8> = key.to_vec();
+    internal_key.extend_from_slice(&((sequence << 8) | 1).to_le_bytes());
+    internal_key
+}
+
+struct TableWriter {
+    out: BufWriter<File>,
+    offset: u64,
+    data_block: BlockBuilder,
+    index_block: BlockBuilder,
+    last_key: Vec<u8>,
+}
+
+impl TableWriter {
+    fn new(out: BufWriter<File>) -> Self {
+        Self {
+            out,
+            offset: 0,
+            data_block: BlockBuilder::default(),
+            index_block: BlockBuilder::default(),
+            last_key: vec![],
+        }
+    }
+
+    fn add(&mut self, key: &[u8], value: &[u8]) -> io::Result<()> {
+        self.data_block.add(key, value);
+        self.last_key = key.to_vec();
+
+        if self.data_block.size() >= BLOCK_SIZE {
+            self.flush_data_block()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the current data block and indexes it by its last key.
+    fn flush_data_block(&mut self) -> io::Result<()> {
+        if self.data_block.is_empty() {
+            return Ok(());
+        }
+
+        let block: BlockBuilder = std::mem::take(&mut self.data_block);
+        let handle: Vec<u8> = self.write_block(&block.finish())?;
+        let last_key: Vec<u8> = self.last_key.clone();
+        self.index_block.add(&last_key, &handle);
+
+        Ok(())
+    }
+
+    /// Writes a block with its trailer and returns the encoded handle pointing to it.
+    fn write_block(&mut self, contents: &[u8]) -> io::Result<Vec<u8>> {
+        // Compression type 0 (none) followed by the checksum of contents and type
+        let mut crc: u32 = crc32c_extend(0, contents);
+        crc = crc32c_extend(crc, &[0]);
+
+        self.out.write_all(contents)?;
+        self.out.write_all(&[0])?;
+        self.out.write_all(&mask_crc(crc).to_le_bytes())?;
+
+        let mut handle: Vec<u8> = vec![];
+        put_varint(&mut handle, self.offset);
+        put_varint(&mut handle, contents.len() as u64);
+
+        self.offset += contents.len() as u64 + 5;
+        Ok(handle)
+    }
+
+    /// Writes the remaining blocks and the footer. Returns the size of the table.
+    fn finish(mut self) -> io::Result<u64> {
+        self.flush_data_block()?;
+
+        let metaindex_handle: Vec<u8> = self.write_block(&BlockBuilder::default().finish())?;
+        let index_block: BlockBuilder = std::mem::take(&mut self.index_block);
+        let index_handle: Vec<u8> = self.write_block(&index_block.finish())?;
+
+        let mut footer: Vec<u8> = vec![];
+        footer.extend_from_slice(&metaindex_handle);
+        footer.extend_from_slice(&index_handle);
+        footer.resize(40, 0);
+        footer.extend_from_slice(&TABLE_MAGIC.to_le_bytes());
+
+        self.out.write_all(&footer)?;
+        self.out.flush()?;
+
+        Ok(self.offset + footer.len() as u64)
+    }
+}
+
+/// Builds a block of prefix-compressed sorted entries.
+struct BlockBuilder {
+    buffer: Vec<u8>,
+    restarts: Vec<u32>,
+    counter: usize,
+    last_key: Vec<u8>,
+}
+
+impl Default for BlockBuilder {
+    fn default() -> Self {
+        Self {
+            buffer: vec![],
+            restarts: vec![0],
+            counter: 0,
+            last_key: vec![],
+        }
+    }
+}
+
+impl BlockBuilder {
+    fn add(&mut self, key: &[u8], value: &[u8]) {
+        let shared: usize = if self.counter < RESTART_INTERVAL {
+            self.last_key
+                .iter()
+                .zip(key)
+                .take_while(|(a, b)| a == b)
+                .count()
+        } else {
+            self.restarts.push(self.buffer.len() as u32);
+            self.counter = 0;
+            0
+        };
+
+        put_varint(&mut self.buffer, shared as u64);
+        put_varint(&mut self.buffer, (key.len() - shared) as u64);
+        put_varint(&mut self.buffer, value.len() as u64);
+        self.buffer.extend_from_slice(&key[shared..]);
+        self.buffer.extend_from_slice(value);
+
+        self.last_key = key.to_vec();
+        self.counter += 1;
+    }
+
+    fn size(&self) -> usize {
+        self.buffer.len() + self.restarts.len() * 4 + 4
+    }
+
+    fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        for restart in &self.restarts {
+            self.buffer.extend_from_slice(&restart.to_le_bytes());
+        }
+        self.buffer
+            .extend_from_slice(&(self.restarts.len() as u32).to_le_bytes());
+        self.buffer
+    }
+}
+
+/// Wraps data in a single record of the log format used by LevelDB's manifest.
+fn log_record(data: &[u8]) -> Vec<u8> {
+    assert!(
+        data.len() + 7 <= LOG_BLOCK_SIZE,
+        "Manifest record too large"
+    );
+
+    // Record type 1 marks a record that is not fragmented
+    let mut crc: u32 = crc32c_extend(0, &[1]);
+    crc = crc32c_extend(crc, data);
+
+    let mut record: Vec<u8> = vec![];
+    record.extend_from_slice(&mask_crc(crc).to_le_bytes());
+    record.extend_from_slice(&(data.len() as u16).to_le_bytes());
+    record.push(1);
+    record.extend_from_slice(data);
+    record
+}
+
+fn put_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn put_length_prefixed(out: &mut Vec<u8>, data: &[u8]) {
+    put_varint(out, data.len() as u64);
+    out.extend_from_slice(data);
+}
+
+/// LevelDB stores checksums rotated and offset so that checksums of data containing
+/// checksums stay well distributed.
+fn mask_crc(crc: u32) -> u32 {
+    crc.rotate_right(15).wrapping_add(0xa282ead8)
+}
+
+const CRC32C_TABLE: [u32; 256] = crc32c_table();
+
+const fn crc32c_table() -> [u32; 256] {
+    let mut table: [u32; 256] = [0; 256];
+    let mut i: usize = 0;
+    while i < 256 {
+        let mut crc: u32 = i as u32;
+        let mut bit: usize = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0x82f63b78
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Continues a CRC-32C (Castagnoli) checksum over more data.
+fn crc32c_extend(crc: u32, data: &[u8]) -> u32 {
+    let mut crc: u32 = !crc;
+    for byte in data {
+        crc = CRC32C_TABLE[((crc ^ u32::from(*byte)) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_varint(data: &[u8], pos: &mut usize) -> u64 {
+        let mut value: u64 = 0;
+        let mut shift: u32 = 0;
+        loop {
+            let byte: u8 = data[*pos];
+            *pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return value;
+            }
+            shift += 7;
+        }
+    }
+
+    /// Reads back the entries of a block written by `BlockBuilder`.
+    fn read_block(block: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let restart_count: usize =
+            u32::from_le_bytes(block[block.len() - 4..].try_into().unwrap()) as usize;
+        let end: usize = block.len() - 4 - restart_count * 4;
+
+        let mut entries: Vec<(Vec<u8>, Vec<u8>)> = vec![];
+        let mut key: Vec<u8> = vec![];
+        let mut pos: usize = 0;
+        while pos < end {
+            let shared: usize = get_varint(block, &mut pos) as usize;
+            let unshared: usize = get_varint(block, &mut pos) as usize;
+            let value_len: usize = get_varint(block, &mut pos) as usize;
+            key.truncate(shared);
+            key.extend_from_slice(&block[pos..pos + unshared]);
+            pos += unshared;
+            entries.push((key.clone(), block[pos..pos + value_len].to_vec()));
+            pos += value_len;
+        }
+        entries
+    }
+
+    /// Contents of the block at an encoded handle, after checking its trailer.
+    fn block_at<'a>(table: &'a [u8], handle: &[u8], pos: &mut usize) -> &'a [u8] {
+        let offset: usize = get_varint(handle, pos) as usize;
+        let size: usize = get_varint(handle, pos) as usize;
+        let contents: &[u8] = &table[offset..offset + size];
+
+        assert_eq!(table[offset + size], 0, "blocks are uncompressed");
+        let crc: u32 = crc32c_extend(crc32c_extend(0, contents), &[0]);
+        let stored: [u8; 4] = table[offset + size + 1..offset + size + 5]
+            .try_into()
+            .unwrap();
+        assert_eq!(u32::from_le_bytes(stored), mask_crc(crc));
+        contents
+    }
+
+    #[test]
+    fn crc32c_matches_known_vector() {
+        assert_eq!(crc32c_extend(0, b"123456789"), 0xe3069283);
+        assert_eq!(
+            crc32c_extend(crc32c_extend(0, b"1234"), b"56789"),
+            0xe3069283
+        );
+    }
+
+    #[test]
+    fn varints_are_little_endian_base_128() {
+        let mut out: Vec<u8> = vec![];
+        put_varint(&mut out, 1);
+        put_varint(&mut out, 300);
+        assert_eq!(out, [0x01, 0xac, 0x02]);
+    }
+
+    #[test]
+    fn table_round_trips_through_footer_and_index() {
+        let path = std::env::temp_dir().join(format!("arnis-leveldb-{}.ldb", std::process::id()));
+        let mut writer: TableWriter =
+            TableWriter::new(BufWriter::new(File::create(&path).unwrap()));
+        // Enough entries for several data blocks and restart points
+        let entries: Vec<(Vec<u8>, Vec<u8>)> = (0..2000u32)
+            .map(|i: u32| {
+                (
+                    internal_key(&i.to_be_bytes(), u64::from(i) + 1),
+                    vec![i as u8; 10],
+                )
+            })
+            .collect();
+        for (key, value) in &entries {
+            writer.add(key, value).unwrap();
+        }
+        let size: u64 = writer.finish().unwrap();
+        let table: Vec<u8> = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(size, table.len() as u64);
+
+        // Footer: two handles padded to 40 bytes, then the magic number
+        let footer: &[u8] = &table[table.len() - 48..];
+        assert_eq!(footer[40..], TABLE_MAGIC.to_le_bytes());
+        let mut pos: usize = 0;
+        assert!(read_block(block_at(&table, footer, &mut pos)).is_empty());
+        let index: Vec<(Vec<u8>, Vec<u8>)> = read_block(block_at(&table, footer, &mut pos));
+        assert!(pos <= 40);
+        assert!(index.len() > 1);
+
+        // Data blocks follow each other from the start of the file
+        let mut read_back: Vec<(Vec<u8>, Vec<u8>)> = vec![];
+        let mut expected_offset: u64 = 0;
+        for (last_key, handle) in &index {
+            let mut handle_pos: usize = 0;
+            assert_eq!(get_varint(handle, &mut handle_pos), expected_offset);
+            let mut handle_pos: usize = 0;
+            let block: Vec<(Vec<u8>, Vec<u8>)> =
+                read_block(block_at(&table, handle, &mut handle_pos));
+            assert_eq!(&block.last().unwrap().0, last_key);
+            expected_offset += block_at(&table, handle, &mut 0).len() as u64 + 5;
+            read_back.extend(block);
+        }
+        assert_eq!(read_back, entries);
+    }
+
+    #[test]
+    fn manifest_record_has_checksum_length_and_type() {
+        let record: Vec<u8> = log_record(b"edit");
+        let crc: u32 = crc32c_extend(crc32c_extend(0, &[1]), b"edit");
+        assert_eq!(record[..4], mask_crc(crc).to_le_bytes());
+        assert_eq!(record[4..6], 4u16.to_le_bytes());
+        assert_eq!(record[6], 1);
+        assert_eq!(&record[7..], b"edit");
+    }
+
+    #[test]
+    fn existing_database_is_not_replaced() {
+        let dir = std::env::temp_dir().join(format!("arnis-leveldb-db-{}", std::process::id()));
+        let mut db: LevelDbWriter = LevelDbWriter::default();
+        db.put(b"key".to_vec(), b"value".to_vec());
+        db.write(&dir).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("CURRENT")).unwrap(),
+            "MANIFEST-000001\n"
+        );
+        assert!(dir.join("000002.ldb").exists());
+
+        let error: io::Error = db.write(&dir).unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+    }
+}
@@ -0,0 +1,405 @@
+mod blocks;
+mod leveldb;
+mod nbt;
+
+use super::block_states::BlockProperties;
+use super::{flat_layers, ChunkToModify, SectionToModify, WorldToModify, MIN_Y, WORLD_HEIGHT};
+use crate::args::Args;
+use crate::biome_definitions::Biome;
+use crate::block_definitions::Block;
+use crate::level_settings::{Difficulty, GameMode, WorldPreset};
+use fastnbt::Value;
+use fnv::FnvHashMap;
+use leveldb::LevelDbWriter;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Chunk format version of Bedrock 1.21.40.
+const CHUNK_VERSION: u8 = 40;
+/// Sub chunk format that stores its own y index.
+const SUB_CHUNK_VERSION: u8 = 9;
+/// Format version of level.dat.
+const STORAGE_VERSION: i32 = 10;
+/// Game version the world was last opened with, as major, minor, patch, revision and beta.
+const GAME_VERSION: [i32; 5] = [1, 21, 40, 0, 0];
+
+// Tags identifying the records of a chunk in the database
+const TAG_DATA_3D: u8 = 43;
+const TAG_VERSION: u8 = 44;
+const TAG_SUB_CHUNK_PREFIX: u8 = 47;
+const TAG_BLOCK_ENTITY: u8 = 49;
+const TAG_FINALIZED_STATE: u8 = 54;
+
+/// Item frame block in place of a Java item frame entity: whether it glows and its facing.
+type FrameBlock = (bool, i8);
+
+/// Writes the generated world as a Bedrock Edition world into the world directory,
+/// which has to be new or empty.
+pub(super) fn save(
+    world: &WorldToModify,
+    args: &Args,
+    spawn: (i32, i32, i32),
+) -> Result<(), String> {
+    let world_dir: &Path = Path::new(&args.path);
+    check_target(world_dir)?;
+    fs::create_dir_all(world_dir)
+        .map_err(|e| format!("Failed to create world directory: {}", e))?;
+
+    let mut db: LevelDbWriter = LevelDbWriter::default();
+
+    for ((region_x, region_z), region) in &world.regions {
+        for ((chunk_x, chunk_z), chunk) in &region.chunks {
+            let chunk_x: i32 = region_x * 32 + chunk_x;
+            let chunk_z: i32 = region_z * 32 + chunk_z;
+            write_chunk(&mut db, chunk, chunk_x, chunk_z, args.winter);
+        }
+    }
+
+    db.write(&world_dir.join("db"))
+        .map_err(|e| format!("Failed to write world database: {}", e))?;
+
+    write_level_dat(world_dir, args, spawn)
+}
+
+/// Checks that a Bedrock world can be written to the directory without overwriting
+/// another world, as existing Bedrock worlds are not merged into.
+pub fn check_target(world_dir: &Path) -> Result<(), String> {
+    let is_empty: bool = match fs::read_dir(world_dir) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(_) => !world_dir.exists(),
+    };
+    if !is_empty {
+        return Err(format!(
+            "{} is not empty, Bedrock worlds are only written to a new or empty directory",
+            world_dir.display()
+        ));
+    }
+
+    Ok(())
+}
+
+fn chunk_key(chunk_x: i32, chunk_z: i32, tag: u8) -> Vec<u8> {
+    let mut key: Vec<u8> = vec![];
+    key.extend_from_slice(&chunk_x.to_le_bytes());
+    key.extend_from_slice(&chunk_z.to_le_bytes());
+    key.push(tag);
+    key
+}
+
+fn write_chunk(
+    db: &mut LevelDbWriter,
+    chunk: &ChunkToModify,
+    chunk_x: i32,
+    chunk_z: i32,
+    winter: bool,
+) {
+    let mut block_entities: Vec<u8> = vec![];
+    for ((x, y, z), entity) in &chunk.block_entities {
+        let nbt: Value = entity.to_bedrock_nbt(
+            chunk_x * 16 + i32::from(*x),
+            *y,
+            chunk_z * 16 + i32::from(*z),
+        );
+        block_entities.extend(nbt::to_bytes(&nbt));
+    }
+
+    // Item frames are blocks with a block entity on Bedrock
+    let mut frames: FnvHashMap<(u8, i32, u8), FrameBlock> = FnvHashMap::default();
+    for ((x, y, z), entity) in &chunk.entities {
+        let (glowing, facing, nbt) = entity.to_bedrock_block(
+            chunk_x * 16 + i32::from(*x),
+            *y,
+            chunk_z * 16 + i32::from(*z),
+        );
+        frames.insert((*x, *y, *z), (glowing, facing));
+        block_entities.extend(nbt::to_bytes(&nbt));
+    }
+
+    let section_ys: BTreeSet<i8> = chunk
+        .sections
+        .keys()
+        .copied()
+        .chain(frames.keys().map(|(_, y, _)| (y >> 4) as i8))
+        .collect();
+    for section_y in section_ys {
+        let mut key: Vec<u8> = chunk_key(chunk_x, chunk_z, TAG_SUB_CHUNK_PREFIX);
+        key.push(section_y as u8);
+        db.put(
+            key,
+            encode_sub_chunk(chunk.sections.get(&section_y), section_y, &frames),
+        );
+    }
+
+    db.put(
+        chunk_key(chunk_x, chunk_z, TAG_DATA_3D),
+        encode_data_3d(chunk, winter),
+    );
+    if !block_entities.is_empty() {
+        db.put(
+            chunk_key(chunk_x, chunk_z, TAG_BLOCK_ENTITY),
+            block_entities,
+        );
+    }
+    db.put(
+        chunk_key(chunk_x, chunk_z, TAG_VERSION),
+        vec![CHUNK_VERSION],
+    );
+    // Fully generated, so the game does not populate the chunk itself
+    db.put(
+        chunk_key(chunk_x, chunk_z, TAG_FINALIZED_STATE),
+        2i32.to_le_bytes().to_vec(),
+    );
+}
+
+/// Index of a block within a Bedrock sub chunk, which orders blocks by x, then z, then y.
+fn bedrock_index(x: usize, y: usize, z: usize) -> usize {
+    (x * 16 + z) * 16 + y
+}
+
+/// Packs palette indices into 32 bit words. Indices never span two words.
+fn pack_words(indices: &[u32], palette_len: usize) -> (u8, Vec<u8>) {
+    let needed: u8 = (usize::BITS - palette_len.saturating_sub(1).leading_zeros()) as u8;
+    let bits: u8 = [1, 2, 3, 4, 5, 6, 8, 16]
+        .into_iter()
+        .find(|bits: &u8| *bits >= needed)
+        .unwrap_or(16);
+
+    let per_word: usize = 32 / usize::from(bits);
+    let mut words: Vec<u32> = vec![0; indices.len().div_ceil(per_word)];
+    for (i, index) in indices.iter().enumerate() {
+        words[i / per_word] |= index << ((i % per_word) * usize::from(bits));
+    }
+
+    (
+        bits,
+        words
+            .iter()
+            .flat_map(|word: &u32| word.to_le_bytes())
+            .collect(),
+    )
+}
+
+fn encode_sub_chunk(
+    section: Option<&SectionToModify>,
+    section_y: i8,
+    frames: &FnvHashMap<(u8, i32, u8), FrameBlock>,
+) -> Vec<u8> {
+    let empty_section: SectionToModify = SectionToModify::default();
+    let section: &SectionToModify = section.unwrap_or(&empty_section);
+
+    type PaletteKey<'a> = (Block, Option<&'a BlockProperties>, Option<FrameBlock>);
+    let mut palette: Vec<Value> = vec![];
+    let mut palette_lookup: FnvHashMap<PaletteKey, u32> = FnvHashMap::default();
+    let mut indices: Vec<u32> = vec![0; 4096];
+
+    for x in 0..16u8 {
+        for z in 0..16u8 {
+            for y in 0..16u8 {
+                let (block, properties) = section.state_at(SectionToModify::index(x, y, z));
+                let frame: Option<FrameBlock> = if block == Block::Air {
+                    frames
+                        .get(&(x, i32::from(section_y) * 16 + i32::from(y), z))
+                        .copied()
+                } else {
+                    None
+                };
+
+                let index: u32 = *palette_lookup
+                    .entry((block, properties, frame))
+                    .or_insert_with(|| {
+                        palette.push(match frame {
+                            Some((glowing, facing)) => blocks::item_frame_state(glowing, facing),
+                            None => blocks::block_state(
+                                block,
+                                &SectionToModify::state_properties(block, properties),
+                            ),
+                        });
+                        (palette.len() - 1) as u32
+                    });
+                indices[bedrock_index(usize::from(x), usize::from(y), usize::from(z))] = index;
+            }
+        }
+    }
+
+    let (bits, words) = pack_words(&indices, palette.len());
+
+    let mut data: Vec<u8> = vec![SUB_CHUNK_VERSION, 1, section_y as u8];
+    data.push(bits << 1); // Palette stored as NBT rather than runtime ids
+    data.extend(words);
+    data.extend_from_slice(&(palette.len() as i32).to_le_bytes());
+    for state in &palette {
+        data.extend(nbt::to_bytes(state));
+    }
+    data
+}
+
+/// Heightmap followed by the 3D biomes of every sub chunk from the bottom of the world up.
+fn encode_data_3d(chunk: &ChunkToModify, winter: bool) -> Vec<u8> {
+    let mut data: Vec<u8> = vec![];
+
+    let mut sections: Vec<(&i8, &SectionToModify)> = chunk.sections.iter().collect();
+    sections.sort_by_key(|(y, _)| -**y);
+    for z in 0..16u8 {
+        for x in 0..16u8 {
+            let height: i32 = sections
+                .iter()
+                .find_map(|(section_y, section)| {
+                    (0..16u8).rev().find_map(|y| {
+                        (section.blocks[SectionToModify::index(x, y, z)] != Block::Air)
+                            .then(|| i32::from(**section_y) * 16 + i32::from(y) + 1 - MIN_Y)
+                    })
+                })
+                .unwrap_or(0);
+            data.extend_from_slice(&(height as i16).to_le_bytes());
+        }
+    }
+
+    let columns: [Biome; 16] = chunk.biome_columns(winter);
+    let mut palette: Vec<i32> = vec![];
+    let mut indices: Vec<u32> = vec![0; 4096];
+    for x in 0..16 {
+        for z in 0..16 {
+            let id: i32 = blocks::biome_id(columns[(z / 4) * 4 + x / 4]);
+            let index: usize = palette
+                .iter()
+                .position(|p: &i32| *p == id)
+                .unwrap_or_else(|| {
+                    palette.push(id);
+                    palette.len() - 1
+                });
+            for y in 0..16 {
+                indices[bedrock_index(x, y, z)] = index as u32;
+            }
+        }
+    }
+
+    // Biomes do not change with height, so every sub chunk gets the same storage
+    let mut storage: Vec<u8> = vec![];
+    if palette.len() == 1 {
+        storage.push(1); // Zero bits per entry, a single biome
+        storage.extend_from_slice(&palette[0].to_le_bytes());
+    } else {
+        let (bits, words) = pack_words(&indices, palette.len());
+        storage.push((bits << 1) | 1);
+        storage.extend(words);
+        storage.extend_from_slice(&(palette.len() as i32).to_le_bytes());
+        for id in &palette {
+            storage.extend_from_slice(&id.to_le_bytes());
+        }
+    }
+
+    for _ in 0..WORLD_HEIGHT / 16 {
+        data.extend_from_slice(&storage);
+    }
+    data
+}
+
+/// Writes level.dat and levelname.txt for a world named after its directory, with the
+/// level settings from the arguments. Unset settings default to a flat creative world.
+fn write_level_dat(world_dir: &Path, args: &Args, spawn: (i32, i32, i32)) -> Result<(), String> {
+    let level_name: String = world_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Arnis World".to_string());
+
+    let now: i64 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0);
+
+    let flat_world_layers: String = flat_world_layers(args);
+
+    let version: Value = Value::List(GAME_VERSION.iter().map(|v: &i32| Value::Int(*v)).collect());
+
+    let entries: BTreeMap<&str, Value> = BTreeMap::from([
+        ("LevelName", Value::String(level_name.clone())),
+        ("StorageVersion", Value::Int(STORAGE_VERSION)),
+        ("Generator", Value::Int(2)), // Flat
+        ("FlatWorldLayers", Value::String(flat_world_layers)),
+        (
+            "GameType",
+            Value::Int(args.gamemode.unwrap_or(GameMode::Creative).id()),
+        ),
+        (
+            "Difficulty",
+            Value::Int(args.difficulty.unwrap_or(Difficulty::Peaceful).id()),
+        ),
+        ("SpawnX", Value::Int(spawn.0)),
+        ("SpawnY", Value::Int(spawn.1)),
+        ("SpawnZ", Value::Int(spawn.2)),
+        ("LastPlayed", Value::Long(now)),
+        ("RandomSeed", Value::Long(0)),
+        ("Time", Value::Long(args.time.unwrap_or(6000))),
+        ("currentTick", Value::Long(0)),
+        ("lastOpenedWithVersion", version.clone()),
+        ("MinimumCompatibleClientVersion", version),
+        ("commandsEnabled", Value::Byte(1)),
+        ("hasBeenLoadedInCreative", Value::Byte(1)),
+        ("showcoordinates", Value::Byte(1)),
+        (
+            "spawnMobs",
+            Value::Byte(i8::from(args.mob_spawning.unwrap_or(false))),
+        ),
+        (
+            "domobspawning",
+            Value::Byte(i8::from(args.mob_spawning.unwrap_or(false))),
+        ),
+        (
+            "dodaylightcycle",
+            Value::Byte(i8::from(args.daylight_cycle.unwrap_or(true))),
+        ),
+        (
+            "doweathercycle",
+            Value::Byte(i8::from(args.weather_cycle.unwrap_or(true))),
+        ),
+    ]);
+    let level: Value = Value::Compound(
+        entries
+            .into_iter()
+            .map(|(key, value): (&str, Value)| (key.to_string(), value))
+            .collect::<HashMap<String, Value>>(),
+    );
+
+    // Header of storage version and payload length, both little-endian
+    let payload: Vec<u8> = nbt::to_bytes(&level);
+    let mut data: Vec<u8> = vec![];
+    data.extend_from_slice(&STORAGE_VERSION.to_le_bytes());
+    data.extend_from_slice(&(payload.len() as i32).to_le_bytes());
+    data.extend(payload);
+
+    fs::write(world_dir.join("level.dat"), data)
+        .map_err(|e| format!("Failed to write level.dat: {}", e))?;
+    fs::write(world_dir.join("levelname.txt"), level_name)
+        .map_err(|e| format!("Failed to write levelname.txt: {}", e))
+}
+
+/// Layers of the flat generator as the JSON Bedrock stores in level.dat, matching the
+/// ground layer of the generated area unless the void preset was chosen.
+fn flat_world_layers(args: &Args) -> String {
+    let layers: Vec<(Block, i32)> = match args.world_preset.unwrap_or(WorldPreset::Flat) {
+        WorldPreset::Flat => flat_layers(args),
+        WorldPreset::Void => vec![(Block::Air, 1)],
+    };
+
+    let biome: Biome = if args.winter {
+        Biome::SnowyPlains
+    } else {
+        Biome::Plains
+    };
+
+    serde_json::json!({
+        "biome_id": blocks::biome_id(biome),
+        "block_layers": layers
+            .iter()
+            .map(|(block, count)| {
+                serde_json::json!({ "block_name": blocks::block_name(*block), "count": count })
+            })
+            .collect::<Vec<serde_json::Value>>(),
+        "encoding_version": 6,
+        "structure_options": null,
+        "world_version": "version.post_1_18",
+    })
+    .to_string()
+}
@@ -0,0 +1,77 @@
+use fastnbt::Value;
+
+/// Serializes an NBT compound in the little-endian flavour used by Bedrock Edition.
+/// The root tag gets an empty name, like Bedrock writes it.
+pub fn to_bytes(root: &Value) -> Vec<u8> {
+    let mut out: Vec<u8> = vec![];
+    out.push(tag_id(root));
+    write_string(&mut out, "");
+    write_payload(&mut out, root);
+    out
+}
+
+fn tag_id(value: &Value) -> u8 {
+    match value {
+        Value::Byte(_) => 1,
+        Value::Short(_) => 2,
+        Value::Int(_) => 3,
+        Value::Long(_) => 4,
+        Value::Float(_) => 5,
+        Value::Double(_) => 6,
+        Value::ByteArray(_) => 7,
+        Value::String(_) => 8,
+        Value::List(_) => 9,
+        Value::Compound(_) => 10,
+        Value::IntArray(_) => 11,
+        Value::LongArray(_) => 12,
+    }
+}
+
+fn write_string(out: &mut Vec<u8>, string: &str) {
+    out.extend_from_slice(&(string.len() as u16).to_le_bytes());
+    out.extend_from_slice(string.as_bytes());
+}
+
+fn write_payload(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Byte(value) => out.push(*value as u8),
+        Value::Short(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::Int(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::Long(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::Float(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::Double(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::ByteArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_le_bytes());
+            out.extend(values.iter().map(|value: &i8| *value as u8));
+        }
+        Value::String(value) => write_string(out, value),
+        Value::List(values) => {
+            // Empty lists are written as lists of end tags
+            out.push(values.first().map(tag_id).unwrap_or(0));
+            out.extend_from_slice(&(values.len() as i32).to_le_bytes());
+            for value in values {
+                write_payload(out, value);
+            }
+        }
+        Value::Compound(entries) => {
+            for (name, value) in entries {
+                out.push(tag_id(value));
+                write_string(out, name);
+                write_payload(out, value);
+            }
+            out.push(0);
+        }
+        Value::IntArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_le_bytes());
+            for value in values.iter() {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        Value::LongArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_le_bytes());
+            for value in values.iter() {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::biome_definitions::Biome;
+use fastnbt::LongArray;
+use serde::{Deserialize, Serialize};
+
+/// Biome palette of a section. Biomes are stored in cells of 4x4x4 blocks.
+#[derive(Serialize, Deserialize)]
+pub struct SectionBiomes {
+    palette: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data: Option<LongArray>,
+}
+
+impl SectionBiomes {
+    /// Builds the biomes of a section from the 4x4 grid of biome columns of its chunk.
+    /// Every vertical layer of cells in the section shares the same columns.
+    pub fn from_columns(columns: &[Biome; 16]) -> Self {
+        let mut palette: Vec<Biome> = columns.to_vec();
+        palette.sort();
+        palette.dedup();
+
+        let data: Option<LongArray> = if palette.len() > 1 {
+            let mut bits_per_entry: usize = 1;
+            while (1 << bits_per_entry) < palette.len() {
+                bits_per_entry += 1;
+            }
+            let entries_per_long: usize = 64 / bits_per_entry;
+
+            // Cell index is (y * 4 + z) * 4 + x, so the 16 columns repeat for each of the 4 layers
+            let indices: Vec<i64> = (0..4)
+                .flat_map(|_| columns.iter())
+                .map(|biome: &Biome| palette.binary_search(biome).unwrap() as i64)
+                .collect();
+
+            let longs: Vec<i64> = indices
+                .chunks(entries_per_long)
+                .map(|entries: &[i64]| {
+                    entries
+                        .iter()
+                        .enumerate()
+                        .fold(0i64, |long: i64, (i, index): (usize, &i64)| {
+                            long | (index << (i * bits_per_entry))
+                        })
+                })
+                .collect();
+
+            Some(LongArray::new(longs))
+        } else {
+            None
+        };
+
+        Self {
+            palette: palette
+                .iter()
+                .map(|biome: &Biome| biome.name().to_string())
+                .collect(),
+            data,
+        }
+    }
+}
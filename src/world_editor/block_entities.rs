@@ -0,0 +1,942 @@
+use super::block_states::BlockProperties;
+use crate::block_definitions::Block;
+use crate::minecraft_version::MinecraftVersion;
+use fastnbt::{IntArray, Value};
+use std::collections::HashMap;
+
+// Builders that no element processor places yet are marked `#[allow(dead_code)]`.
+
+/// Horizontal direction an entity or block faces, numbered like Minecraft does.
+#[allow(dead_code)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Facing {
+    North = 2,
+    South = 3,
+    West = 4,
+    East = 5,
+}
+
+impl Facing {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::North => "north",
+            Self::South => "south",
+            Self::West => "west",
+            Self::East => "east",
+        }
+    }
+
+    fn id(&self) -> i8 {
+        *self as i8
+    }
+
+    /// The x and z step to the neighbouring block in this direction.
+    fn offset(&self) -> (i32, i32) {
+        match self {
+            Self::North => (0, -1),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+            Self::East => (1, 0),
+        }
+    }
+}
+
+/// Builds an NBT compound from key/value pairs.
+fn compound(entries: Vec<(&str, Value)>) -> Value {
+    Value::Compound(
+        entries
+            .into_iter()
+            .map(|(key, value): (&str, Value)| (key.to_string(), value))
+            .collect::<HashMap<String, Value>>(),
+    )
+}
+
+/// Characters that fit on one line of a sign.
+const SIGN_LINE_LENGTH: usize = 15;
+
+/// Plain text as a JSON text component.
+fn text_component(text: &str) -> Value {
+    Value::String(serde_json::to_string(text).unwrap())
+}
+
+/// Dye colors in the order of their Java Edition ids.
+const DYE_COLORS: [&str; 16] = [
+    "white",
+    "orange",
+    "magenta",
+    "light_blue",
+    "yellow",
+    "lime",
+    "pink",
+    "gray",
+    "light_gray",
+    "cyan",
+    "purple",
+    "blue",
+    "brown",
+    "green",
+    "red",
+    "black",
+];
+
+/// Java Edition dye id of a color name, defaulting to black.
+fn dye_id(color: &str) -> usize {
+    DYE_COLORS
+        .iter()
+        .position(|dye: &&str| *dye == color)
+        .unwrap_or(15)
+}
+
+/// Item stack in the format Java Edition stores in containers. The count became an int
+/// together with the switch to item components in 1.20.5.
+fn java_item(id: &str, count: i32, version: MinecraftVersion) -> Vec<(&'static str, Value)> {
+    let count: (&'static str, Value) = if version.has_item_components() {
+        ("count", Value::Int(count))
+    } else {
+        ("Count", Value::Byte(count.clamp(0, 127) as i8))
+    };
+
+    vec![("id", Value::String(id.to_string())), count]
+}
+
+/// Item stack in the format Bedrock Edition stores in containers.
+fn bedrock_item(id: &str, count: i32) -> Vec<(&'static str, Value)> {
+    vec![
+        ("Name", Value::String(id.to_string())),
+        ("Count", Value::Byte(count.clamp(0, 127) as i8)),
+        ("Damage", Value::Short(0)),
+        ("WasPickedUp", Value::Byte(0)),
+    ]
+}
+
+/// A block entity together with the data stored alongside its block.
+pub enum BlockEntity {
+    Sign(Sign),
+    Container(Container),
+    Banner(Banner),
+    Lectern(Lectern),
+}
+
+impl BlockEntity {
+    /// The block the entity belongs to.
+    pub fn block(&self) -> Block {
+        match self {
+            Self::Sign(sign) => match (sign.hanging, sign.wall) {
+                (false, None) => Block::Sign,
+                (false, Some(_)) => Block::OakWallSign,
+                (true, None) => Block::OakHangingSign,
+                (true, Some(_)) => Block::OakWallHangingSign,
+            },
+            Self::Container(container) => match container.kind {
+                ContainerKind::Chest => Block::Chest,
+                ContainerKind::Barrel => Block::Barrel,
+            },
+            Self::Banner(_) => Block::WhiteBanner,
+            Self::Lectern(_) => Block::Lectern,
+        }
+    }
+
+    /// Block state properties that depend on how the entity was built, such as sign rotation.
+    pub fn properties(&self) -> Option<BlockProperties> {
+        match self {
+            Self::Sign(sign) => Some(sign.properties()),
+            _ => None,
+        }
+    }
+
+    /// The entity adjusted to what exists in the given version, e.g. hanging signs
+    /// become regular signs before 1.20.
+    pub fn for_version(self, version: MinecraftVersion) -> Self {
+        match self {
+            Self::Sign(mut sign) if !version.has_two_sided_signs() => {
+                sign.hanging = false;
+                Self::Sign(sign)
+            }
+            entity => entity,
+        }
+    }
+
+    pub fn to_nbt(&self, x: i32, y: i32, z: i32, version: MinecraftVersion) -> Value {
+        let mut entries: Vec<(&str, Value)> = match self {
+            Self::Sign(sign) => sign.entries(version),
+            Self::Container(container) => container.entries(version),
+            Self::Banner(banner) => banner.entries(version),
+            Self::Lectern(lectern) => lectern.entries(version),
+        };
+
+        entries.push(("keepPacked", Value::Byte(0)));
+        entries.push(("x", Value::Int(x)));
+        entries.push(("y", Value::Int(y)));
+        entries.push(("z", Value::Int(z)));
+
+        compound(entries)
+    }
+}
+
+impl BlockEntity {
+    /// The block entity in the format of Bedrock Edition.
+    pub fn to_bedrock_nbt(&self, x: i32, y: i32, z: i32) -> Value {
+        let mut entries: Vec<(&str, Value)> = match self {
+            Self::Sign(sign) => sign.bedrock_entries(),
+            Self::Container(container) => container.bedrock_entries(),
+            Self::Banner(banner) => banner.bedrock_entries(),
+            Self::Lectern(lectern) => lectern.bedrock_entries(),
+        };
+
+        entries.push(("isMovable", Value::Byte(1)));
+        entries.push(("x", Value::Int(x)));
+        entries.push(("y", Value::Int(y)));
+        entries.push(("z", Value::Int(z)));
+
+        compound(entries)
+    }
+}
+
+impl From<Sign> for BlockEntity {
+    fn from(sign: Sign) -> Self {
+        Self::Sign(sign)
+    }
+}
+
+impl From<Container> for BlockEntity {
+    fn from(container: Container) -> Self {
+        Self::Container(container)
+    }
+}
+
+impl From<Banner> for BlockEntity {
+    fn from(banner: Banner) -> Self {
+        Self::Banner(banner)
+    }
+}
+
+impl From<Lectern> for BlockEntity {
+    fn from(lectern: Lectern) -> Self {
+        Self::Lectern(lectern)
+    }
+}
+
+/// Text on one side of a sign.
+struct SignText {
+    lines: [String; 4],
+    color: String,
+    glowing: bool,
+}
+
+impl SignText {
+    fn new(lines: [String; 4]) -> Self {
+        Self {
+            lines,
+            color: "black".to_string(),
+            glowing: false,
+        }
+    }
+
+    fn to_nbt(&self) -> Value {
+        compound(vec![
+            (
+                "messages",
+                Value::List(
+                    self.lines
+                        .iter()
+                        .map(|line: &String| text_component(line))
+                        .collect(),
+                ),
+            ),
+            ("color", Value::String(self.color.clone())),
+            ("has_glowing_text", Value::Byte(i8::from(self.glowing))),
+        ])
+    }
+
+    fn to_bedrock_nbt(&self) -> Value {
+        // Bedrock stores the text color as ARGB
+        const TEXT_COLORS: [u32; 16] = [
+            0xFFFFFF, 0xFF681F, 0xFF00FF, 0x9AC0CD, 0xFFFF00, 0xBFFF00, 0xFF69B4, 0x808080,
+            0xD3D3D3, 0x00FFFF, 0xA020F0, 0x0000FF, 0x8B4513, 0x00FF00, 0xFF0000, 0x000000,
+        ];
+        let color: u32 = 0xFF000000 | TEXT_COLORS[dye_id(&self.color)];
+
+        compound(vec![
+            ("Text", Value::String(self.lines.join("\n"))),
+            ("TextOwner", Value::String(String::new())),
+            ("SignTextColor", Value::Int(color as i32)),
+            ("IgnoreLighting", Value::Byte(i8::from(self.glowing))),
+            ("HideGlowOutline", Value::Byte(0)),
+            ("PersistFormatting", Value::Byte(1)),
+        ])
+    }
+}
+
+/// Splits text into the four lines of a sign, breaking between words where possible.
+/// Text that does not fit is cut off with an ellipsis on the last line.
+pub fn wrap_sign_text(text: &str) -> [String; 4] {
+    let mut lines: Vec<String> = vec![];
+    let mut current: String = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+
+        // Words longer than a line are split over several lines
+        while word.len() > SIGN_LINE_LENGTH {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            lines.push(word.drain(..SIGN_LINE_LENGTH).collect());
+        }
+
+        let word: String = word.into_iter().collect();
+        if current.is_empty() {
+            current = word;
+        } else if current.chars().count() + 1 + word.chars().count() <= SIGN_LINE_LENGTH {
+            current.push(' ');
+            current.push_str(&word);
+        } else {
+            lines.push(std::mem::replace(&mut current, word));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > 4 {
+        let mut last: Vec<char> = lines[3].chars().collect();
+        last.truncate(SIGN_LINE_LENGTH - 1);
+        lines[3] = last.into_iter().collect::<String>() + "…";
+        lines.truncate(4);
+    }
+
+    let mut wrapped: [String; 4] = Default::default();
+    for (slot, line) in wrapped.iter_mut().zip(lines) {
+        *slot = line;
+    }
+    wrapped
+}
+
+/// A standing, wall-mounted or hanging sign with up to four lines of text on each side.
+pub struct Sign {
+    front: SignText,
+    back: SignText,
+    rotation: i8,
+    wall: Option<Facing>,
+    hanging: bool,
+    waxed: bool,
+}
+
+impl Sign {
+    pub fn new(lines: [String; 4]) -> Self {
+        Self {
+            front: SignText::new(lines),
+            back: SignText::new(Default::default()),
+            rotation: 0,
+            wall: None,
+            hanging: false,
+            waxed: false,
+        }
+    }
+
+    /// Creates a sign showing the given text, wrapped over its four lines.
+    pub fn wrapped(text: &str) -> Self {
+        Self::new(wrap_sign_text(text))
+    }
+
+    /// Rotation of a free-standing sign in steps of 22.5 degrees (0-15), 0 facing south.
+    pub fn rotation(mut self, rotation: i8) -> Self {
+        self.rotation = rotation.rem_euclid(16);
+        self
+    }
+
+    /// Mounts the sign on the side of the block behind it, facing the given direction.
+    #[allow(dead_code)]
+    pub fn wall(mut self, facing: Facing) -> Self {
+        self.wall = Some(facing);
+        self
+    }
+
+    /// Turns the sign into a hanging sign.
+    #[allow(dead_code)]
+    pub fn hanging(mut self) -> Self {
+        self.hanging = true;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn back(mut self, lines: [String; 4]) -> Self {
+        self.back.lines = lines;
+        self
+    }
+
+    /// Dye color of the text on both sides.
+    #[allow(dead_code)]
+    pub fn color(mut self, color: &str) -> Self {
+        self.front.color = color.to_string();
+        self.back.color = color.to_string();
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn glowing(mut self) -> Self {
+        self.front.glowing = true;
+        self.back.glowing = true;
+        self
+    }
+
+    /// Prevents players from editing the text.
+    #[allow(dead_code)]
+    pub fn waxed(mut self) -> Self {
+        self.waxed = true;
+        self
+    }
+
+    fn properties(&self) -> BlockProperties {
+        let mut properties: BlockProperties = BlockProperties::new();
+        match self.wall {
+            Some(facing) => {
+                properties.insert("facing".to_string(), facing.name().to_string());
+            }
+            None => {
+                properties.insert("rotation".to_string(), self.rotation.to_string());
+            }
+        }
+        if self.hanging && self.wall.is_none() {
+            properties.insert("attached".to_string(), "false".to_string());
+        }
+        properties.insert("waterlogged".to_string(), "false".to_string());
+        properties
+    }
+
+    fn entries(&self, version: MinecraftVersion) -> Vec<(&str, Value)> {
+        let id: &str = if self.hanging {
+            "minecraft:hanging_sign"
+        } else {
+            "minecraft:sign"
+        };
+
+        // Before 1.20 signs only had a front side with the lines stored individually
+        if !version.has_two_sided_signs() {
+            let [text1, text2, text3, text4] = &self.front.lines;
+            return vec![
+                ("id", Value::String(id.to_string())),
+                ("Text1", text_component(text1)),
+                ("Text2", text_component(text2)),
+                ("Text3", text_component(text3)),
+                ("Text4", text_component(text4)),
+                ("Color", Value::String(self.front.color.clone())),
+                ("GlowingText", Value::Byte(i8::from(self.front.glowing))),
+            ];
+        }
+
+        vec![
+            ("id", Value::String(id.to_string())),
+            ("front_text", self.front.to_nbt()),
+            ("back_text", self.back.to_nbt()),
+            ("is_waxed", Value::Byte(i8::from(self.waxed))),
+        ]
+    }
+
+    fn bedrock_entries(&self) -> Vec<(&str, Value)> {
+        let id: &str = if self.hanging { "HangingSign" } else { "Sign" };
+
+        vec![
+            ("id", Value::String(id.to_string())),
+            ("FrontText", self.front.to_bedrock_nbt()),
+            ("BackText", self.back.to_bedrock_nbt()),
+            ("IsWaxed", Value::Byte(i8::from(self.waxed))),
+        ]
+    }
+}
+
+#[allow(dead_code)]
+enum ContainerKind {
+    Chest,
+    Barrel,
+}
+
+/// A chest or barrel, filled either with fixed items or from a loot table.
+pub struct Container {
+    kind: ContainerKind,
+    items: Vec<(i8, String, i32)>,
+    loot_table: Option<String>,
+    loot_table_seed: Option<i64>,
+}
+
+#[allow(dead_code)]
+impl Container {
+    pub fn chest() -> Self {
+        Self::new(ContainerKind::Chest)
+    }
+
+    pub fn barrel() -> Self {
+        Self::new(ContainerKind::Barrel)
+    }
+
+    fn new(kind: ContainerKind) -> Self {
+        Self {
+            kind,
+            items: vec![],
+            loot_table: None,
+            loot_table_seed: None,
+        }
+    }
+
+    /// Puts a stack of items into the given slot (0-26).
+    pub fn item(mut self, slot: i8, id: &str, count: i32) -> Self {
+        self.items.retain(|(s, _, _)| *s != slot);
+        self.items.push((slot, id.to_string(), count));
+        self
+    }
+
+    /// Fills the container from a loot table, e.g. `minecraft:chests/village/village_weaponsmith`,
+    /// the first time it is opened.
+    pub fn loot_table(mut self, loot_table: &str) -> Self {
+        self.loot_table = Some(loot_table.to_string());
+        self
+    }
+
+    pub fn loot_table_seed(mut self, seed: i64) -> Self {
+        self.loot_table_seed = Some(seed);
+        self
+    }
+
+    fn entries(&self, version: MinecraftVersion) -> Vec<(&str, Value)> {
+        let id: &str = match self.kind {
+            ContainerKind::Chest => "minecraft:chest",
+            ContainerKind::Barrel => "minecraft:barrel",
+        };
+
+        let items: Vec<Value> = self
+            .items
+            .iter()
+            .map(|(slot, id, count)| {
+                let mut item: Vec<(&str, Value)> = java_item(id, *count, version);
+                item.push(("Slot", Value::Byte(*slot)));
+                compound(item)
+            })
+            .collect();
+
+        let mut entries: Vec<(&str, Value)> = vec![
+            ("id", Value::String(id.to_string())),
+            ("Items", Value::List(items)),
+        ];
+        if let Some(loot_table) = &self.loot_table {
+            entries.push(("LootTable", Value::String(loot_table.clone())));
+        }
+        if let Some(seed) = self.loot_table_seed {
+            entries.push(("LootTableSeed", Value::Long(seed)));
+        }
+
+        entries
+    }
+
+    fn bedrock_entries(&self) -> Vec<(&str, Value)> {
+        let id: &str = match self.kind {
+            ContainerKind::Chest => "Chest",
+            ContainerKind::Barrel => "Barrel",
+        };
+
+        let items: Vec<Value> = self
+            .items
+            .iter()
+            .map(|(slot, id, count)| {
+                let mut item: Vec<(&str, Value)> = bedrock_item(id, *count);
+                item.push(("Slot", Value::Byte(*slot)));
+                compound(item)
+            })
+            .collect();
+
+        let mut entries: Vec<(&str, Value)> = vec![
+            ("id", Value::String(id.to_string())),
+            ("Items", Value::List(items)),
+            ("Findable", Value::Byte(0)),
+        ];
+        // Bedrock refers to loot tables by their path in the behavior pack
+        if let Some(loot_table) = &self.loot_table {
+            let path: &str = loot_table.trim_start_matches("minecraft:");
+            entries.push((
+                "LootTable",
+                Value::String(format!("loot_tables/{}.json", path)),
+            ));
+        }
+        if let Some(seed) = self.loot_table_seed {
+            entries.push(("LootTableSeed", Value::Int(seed as i32)));
+        }
+
+        entries
+    }
+}
+
+/// A white standing banner with patterns layered on top.
+pub struct Banner {
+    patterns: Vec<(String, String)>,
+}
+
+impl Banner {
+    pub fn new() -> Self {
+        Self { patterns: vec![] }
+    }
+
+    /// Adds a pattern layer, e.g. `("minecraft:stripe_top", "red")`.
+    #[allow(dead_code)]
+    pub fn pattern(mut self, pattern: &str, color: &str) -> Self {
+        self.patterns.push((pattern.to_string(), color.to_string()));
+        self
+    }
+
+    fn entries(&self, version: MinecraftVersion) -> Vec<(&str, Value)> {
+        // Before 1.20.5 patterns were stored by short code and dye id
+        if !version.has_item_components() {
+            let patterns: Vec<Value> = self
+                .patterns
+                .iter()
+                .filter_map(|(pattern, color)| {
+                    Some(compound(vec![
+                        ("Pattern", Value::String(pattern_code(pattern)?.to_string())),
+                        ("Color", Value::Int(dye_id(color) as i32)),
+                    ]))
+                })
+                .collect();
+
+            return vec![
+                ("id", Value::String("minecraft:banner".to_string())),
+                ("Patterns", Value::List(patterns)),
+            ];
+        }
+
+        let patterns: Vec<Value> = self
+            .patterns
+            .iter()
+            .map(|(pattern, color)| {
+                compound(vec![
+                    ("pattern", Value::String(pattern.clone())),
+                    ("color", Value::String(color.clone())),
+                ])
+            })
+            .collect();
+
+        vec![
+            ("id", Value::String("minecraft:banner".to_string())),
+            ("patterns", Value::List(patterns)),
+        ]
+    }
+
+    fn bedrock_entries(&self) -> Vec<(&str, Value)> {
+        // Bedrock numbers dye colors in reverse and names patterns by short codes.
+        // Patterns without a code are left out.
+        let patterns: Vec<Value> = self
+            .patterns
+            .iter()
+            .filter_map(|(pattern, color)| {
+                let code: &str = pattern_code(pattern)?;
+
+                Some(compound(vec![
+                    ("Pattern", Value::String(code.to_string())),
+                    ("Color", Value::Int(15 - dye_id(color) as i32)),
+                ]))
+            })
+            .collect();
+
+        vec![
+            ("id", Value::String("Banner".to_string())),
+            ("Base", Value::Int(15 - dye_id("white") as i32)),
+            ("Patterns", Value::List(patterns)),
+            ("Type", Value::Int(0)),
+        ]
+    }
+}
+
+/// Short code of a banner pattern, used by Bedrock Edition and by Java Edition before 1.20.5.
+fn pattern_code(pattern: &str) -> Option<&'static str> {
+    let code: &str = match pattern.trim_start_matches("minecraft:") {
+        "stripe_bottom" => "bs",
+        "stripe_top" => "ts",
+        "stripe_left" => "ls",
+        "stripe_right" => "rs",
+        "stripe_center" => "cs",
+        "stripe_middle" => "ms",
+        "stripe_downright" => "drs",
+        "stripe_downleft" => "dls",
+        "small_stripes" => "ss",
+        "cross" => "cr",
+        "straight_cross" => "sc",
+        "diagonal_left" => "ld",
+        "diagonal_right" => "rud",
+        "diagonal_up_left" => "lud",
+        "diagonal_up_right" => "rd",
+        "half_vertical" => "vh",
+        "half_vertical_right" => "vhr",
+        "half_horizontal" => "hh",
+        "half_horizontal_bottom" => "hhb",
+        "square_bottom_left" => "bl",
+        "square_bottom_right" => "br",
+        "square_top_left" => "tl",
+        "square_top_right" => "tr",
+        "triangle_bottom" => "bt",
+        "triangle_top" => "tt",
+        "triangles_bottom" => "bts",
+        "triangles_top" => "tts",
+        "circle" => "mc",
+        "rhombus" => "mr",
+        "border" => "bo",
+        "curly_border" => "cbo",
+        "bricks" => "bri",
+        "gradient" => "gra",
+        "gradient_up" => "gru",
+        "creeper" => "cre",
+        "skull" => "sku",
+        "flower" => "flo",
+        "mojang" => "moj",
+        "globe" => "glb",
+        "piglin" => "pig",
+        _ => return None,
+    };
+    Some(code)
+}
+
+impl Default for Banner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A lectern holding a written book.
+pub struct Lectern {
+    title: String,
+    author: String,
+    pages: Vec<String>,
+}
+
+#[allow(dead_code)]
+impl Lectern {
+    pub fn new(title: &str, author: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            author: author.to_string(),
+            pages: vec![],
+        }
+    }
+
+    pub fn page(mut self, text: &str) -> Self {
+        self.pages.push(text.to_string());
+        self
+    }
+
+    fn entries(&self, version: MinecraftVersion) -> Vec<(&str, Value)> {
+        let mut book: Vec<(&str, Value)> = java_item("minecraft:written_book", 1, version);
+        book.push(if version.has_item_components() {
+            ("components", self.book_components())
+        } else {
+            ("tag", self.book_tag())
+        });
+
+        vec![
+            ("id", Value::String("minecraft:lectern".to_string())),
+            ("Book", compound(book)),
+            ("Page", Value::Int(0)),
+        ]
+    }
+
+    fn book_components(&self) -> Value {
+        let pages: Vec<Value> = self
+            .pages
+            .iter()
+            .map(|page: &String| compound(vec![("raw", text_component(page))]))
+            .collect();
+
+        let book_content: Value = compound(vec![
+            (
+                "title",
+                compound(vec![("raw", Value::String(self.title.clone()))]),
+            ),
+            ("author", Value::String(self.author.clone())),
+            ("pages", Value::List(pages)),
+        ]);
+
+        compound(vec![("minecraft:written_book_content", book_content)])
+    }
+
+    /// Book contents as item NBT, used before 1.20.5.
+    fn book_tag(&self) -> Value {
+        let pages: Vec<Value> = self
+            .pages
+            .iter()
+            .map(|page: &String| text_component(page))
+            .collect();
+
+        compound(vec![
+            ("title", Value::String(self.title.clone())),
+            ("author", Value::String(self.author.clone())),
+            ("pages", Value::List(pages)),
+            ("resolved", Value::Byte(1)),
+        ])
+    }
+
+    fn bedrock_entries(&self) -> Vec<(&str, Value)> {
+        let pages: Vec<Value> = self
+            .pages
+            .iter()
+            .map(|page: &String| {
+                compound(vec![
+                    ("text", Value::String(page.clone())),
+                    ("photoname", Value::String(String::new())),
+                ])
+            })
+            .collect();
+
+        let tag: Value = compound(vec![
+            ("title", Value::String(self.title.clone())),
+            ("author", Value::String(self.author.clone())),
+            ("pages", Value::List(pages)),
+            ("generation", Value::Int(0)),
+            ("xuid", Value::Long(0)),
+        ]);
+
+        let mut book: Vec<(&str, Value)> = bedrock_item("minecraft:written_book", 1);
+        book.push(("tag", tag));
+
+        vec![
+            ("id", Value::String("Lectern".to_string())),
+            ("book", compound(book)),
+            ("hasBook", Value::Byte(1)),
+            ("page", Value::Int(0)),
+            ("totalPages", Value::Int(self.pages.len() as i32)),
+        ]
+    }
+}
+
+/// An entity placed in the world. Entities are stored in the separate entities region files.
+pub enum Entity {
+    ItemFrame(ItemFrame),
+}
+
+impl Entity {
+    pub fn to_nbt(&self, x: i32, y: i32, z: i32, version: MinecraftVersion) -> Value {
+        match self {
+            Self::ItemFrame(frame) => frame.to_nbt(x, y, z, version),
+        }
+    }
+
+    /// Bedrock Edition has no item frame entities. Frames are blocks there, returned
+    /// as whether the frame glows, its facing id and the NBT of its block entity.
+    pub fn to_bedrock_block(&self, x: i32, y: i32, z: i32) -> (bool, i8, Value) {
+        match self {
+            Self::ItemFrame(frame) => frame.to_bedrock_block(x, y, z),
+        }
+    }
+}
+
+impl From<ItemFrame> for Entity {
+    fn from(frame: ItemFrame) -> Self {
+        Self::ItemFrame(frame)
+    }
+}
+
+/// An item frame hanging on the block behind it, optionally holding an item.
+pub struct ItemFrame {
+    facing: Facing,
+    item: Option<String>,
+    glowing: bool,
+    fixed: bool,
+}
+
+#[allow(dead_code)]
+impl ItemFrame {
+    /// Creates a frame facing away from the block it is attached to.
+    pub fn new(facing: Facing) -> Self {
+        Self {
+            facing,
+            item: None,
+            glowing: false,
+            fixed: false,
+        }
+    }
+
+    pub fn item(mut self, id: &str) -> Self {
+        self.item = Some(id.to_string());
+        self
+    }
+
+    pub fn glowing(mut self) -> Self {
+        self.glowing = true;
+        self
+    }
+
+    /// Makes the frame indestructible and its item fixed in place.
+    pub fn fixed(mut self) -> Self {
+        self.fixed = true;
+        self
+    }
+
+    fn to_bedrock_block(&self, x: i32, y: i32, z: i32) -> (bool, i8, Value) {
+        let id: &str = if self.glowing {
+            "GlowItemFrame"
+        } else {
+            "ItemFrame"
+        };
+
+        let mut entries: Vec<(&str, Value)> = vec![
+            ("id", Value::String(id.to_string())),
+            ("isMovable", Value::Byte(1)),
+            ("x", Value::Int(x)),
+            ("y", Value::Int(y)),
+            ("z", Value::Int(z)),
+        ];
+        if let Some(item) = &self.item {
+            entries.push(("Item", compound(bedrock_item(item, 1))));
+            entries.push(("ItemDropChance", Value::Float(1.0)));
+        }
+
+        (self.glowing, self.facing.id(), compound(entries))
+    }
+
+    fn to_nbt(&self, x: i32, y: i32, z: i32, version: MinecraftVersion) -> Value {
+        let id: &str = if self.glowing {
+            "minecraft:glow_item_frame"
+        } else {
+            "minecraft:item_frame"
+        };
+
+        // The frame sits flat against the block behind it
+        let (dx, dz) = self.facing.offset();
+        let position: Vec<Value> = vec![
+            Value::Double(f64::from(x) + 0.5 - f64::from(dx) * 0.46875),
+            Value::Double(f64::from(y) + 0.5),
+            Value::Double(f64::from(z) + 0.5 - f64::from(dz) * 0.46875),
+        ];
+
+        let yaw: f32 = match self.facing {
+            Facing::South => 0.0,
+            Facing::West => 90.0,
+            Facing::North => 180.0,
+            Facing::East => 270.0,
+        };
+        let pitch: f32 = 0.0;
+
+        let uuid: Vec<i32> = (0..4).map(|_| rand::random::<i32>()).collect();
+
+        let mut entries: Vec<(&str, Value)> = vec![
+            ("id", Value::String(id.to_string())),
+            ("Pos", Value::List(position)),
+            ("Motion", Value::List(vec![Value::Double(0.0); 3])),
+            (
+                "Rotation",
+                Value::List(vec![Value::Float(yaw), Value::Float(pitch)]),
+            ),
+            ("UUID", Value::IntArray(IntArray::new(uuid))),
+            ("TileX", Value::Int(x)),
+            ("TileY", Value::Int(y)),
+            ("TileZ", Value::Int(z)),
+            ("Facing", Value::Byte(self.facing.id())),
+            ("ItemDropChance", Value::Float(1.0)),
+            ("Fixed", Value::Byte(i8::from(self.fixed))),
+            ("Invulnerable", Value::Byte(i8::from(self.fixed))),
+            ("OnGround", Value::Byte(0)),
+            ("Air", Value::Short(300)),
+            ("Fire", Value::Short(-1)),
+            ("FallDistance", Value::Float(0.0)),
+        ];
+        if let Some(item) = &self.item {
+            entries.push(("Item", compound(java_item(item, 1, version))));
+        }
+
+        compound(entries)
+    }
+}
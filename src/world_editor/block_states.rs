@@ -0,0 +1,317 @@
+use super::WorldToModify;
+use crate::block_definitions::Block;
+use std::collections::BTreeMap;
+
+/// Block state properties that depend on the neighbouring blocks, such as rail shapes
+/// or fence connections. Keyed by property name, as written to the palette.
+pub type BlockProperties = BTreeMap<String, String>;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::North => "north",
+            Self::East => "east",
+            Self::South => "south",
+            Self::West => "west",
+        }
+    }
+
+    fn offset(&self) -> (i32, i32) {
+        match self {
+            Self::North => (0, -1),
+            Self::East => (1, 0),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+        }
+    }
+
+    fn opposite(&self) -> Direction {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        }
+    }
+
+    /// The direction to the left when looking in this direction.
+    fn left(&self) -> Direction {
+        match self {
+            Self::North => Self::West,
+            Self::East => Self::North,
+            Self::South => Self::East,
+            Self::West => Self::South,
+        }
+    }
+}
+
+/// Computes the orientation and connection states of every directional or connectable
+/// block in the world from its neighbours. Runs once all blocks have been placed.
+pub fn apply(world: &mut WorldToModify) {
+    let mut updates: Vec<(i32, i32, i32, BlockProperties)> = vec![];
+
+    for ((region_x, region_z), region) in &world.regions {
+        for ((chunk_x, chunk_z), chunk) in &region.chunks {
+            for (section_y, section) in &chunk.sections {
+                for (index, block) in section.blocks.iter().enumerate() {
+                    if !has_neighbour_dependent_state(*block) {
+                        continue;
+                    }
+
+                    let x: i32 = (region_x * 32 + chunk_x) * 16 + (index % 16) as i32;
+                    let y: i32 = i32::from(*section_y) * 16 + (index / 256) as i32;
+                    let z: i32 = (region_z * 32 + chunk_z) * 16 + (index / 16 % 16) as i32;
+
+                    if let Some(properties) = compute(world, *block, x, y, z) {
+                        updates.push((x, y, z, properties));
+                    }
+                }
+            }
+        }
+    }
+
+    for (x, y, z, properties) in updates {
+        world.set_properties(x, y, z, properties);
+    }
+}
+
+fn has_neighbour_dependent_state(block: Block) -> bool {
+    block == Block::Rail
+        || is_door(block)
+        || is_fence(block)
+        || is_wall(block)
+        || is_pane(block)
+        || is_stairs(block)
+}
+
+fn compute(world: &WorldToModify, block: Block, x: i32, y: i32, z: i32) -> Option<BlockProperties> {
+    if block == Block::Rail {
+        Some(rail_properties(world, x, y, z))
+    } else if is_door(block) {
+        // Both halves take their orientation from the lower half
+        let lower_y: i32 = if block == Block::DarkOakDoorUpper {
+            y - 1
+        } else {
+            y
+        };
+        Some(door_properties(world, block, x, lower_y, z))
+    } else if is_fence(block) || is_pane(block) {
+        Some(connection_properties(world, block, x, y, z))
+    } else if is_wall(block) {
+        Some(wall_properties(world, x, y, z))
+    } else if is_stairs(block) {
+        Some(stairs_properties(world, x, y, z))
+    } else {
+        None
+    }
+}
+
+fn neighbour(world: &WorldToModify, x: i32, y: i32, z: i32, direction: Direction) -> Block {
+    let (dx, dz) = direction.offset();
+    world.get_block(x + dx, y, z + dz).unwrap_or(Block::Air)
+}
+
+fn is_door(block: Block) -> bool {
+    matches!(block, Block::DarkOakDoorLower | Block::DarkOakDoorUpper)
+}
+
+fn is_fence(block: Block) -> bool {
+    matches!(block, Block::OakFence)
+}
+
+fn is_wall(block: Block) -> bool {
+    matches!(block, Block::CobblestoneWall)
+}
+
+fn is_pane(block: Block) -> bool {
+    matches!(block, Block::Glass | Block::IronBars)
+}
+
+fn is_stairs(block: Block) -> bool {
+    matches!(block, Block::OakStairs | Block::StoneBrickStairs)
+}
+
+fn connects_to(block: Block, other: Block) -> bool {
+    if other.is_solid() {
+        return true;
+    }
+
+    if is_fence(block) {
+        is_fence(other)
+    } else if is_wall(block) || is_pane(block) {
+        is_wall(other) || is_pane(other)
+    } else {
+        false
+    }
+}
+
+/// Rails follow the neighbouring rails, sloping up where the next rail is one block higher.
+fn rail_properties(world: &WorldToModify, x: i32, y: i32, z: i32) -> BlockProperties {
+    let mut connections: Vec<(Direction, bool)> = vec![];
+    for direction in Direction::ALL {
+        let (dx, dz) = direction.offset();
+        let ascending: bool = world.get_block(x + dx, y + 1, z + dz) == Some(Block::Rail);
+        let connected: bool = ascending
+            || world.get_block(x + dx, y, z + dz) == Some(Block::Rail)
+            || world.get_block(x + dx, y - 1, z + dz) == Some(Block::Rail);
+        if connected {
+            connections.push((direction, ascending));
+        }
+    }
+
+    let has = |direction: Direction| connections.iter().any(|(d, _)| *d == direction);
+    let ascends = |direction: Direction| connections.contains(&(direction, true));
+
+    let shape: &str = if has(Direction::North) && has(Direction::South) {
+        if ascends(Direction::North) {
+            "ascending_north"
+        } else if ascends(Direction::South) {
+            "ascending_south"
+        } else {
+            "north_south"
+        }
+    } else if has(Direction::East) && has(Direction::West) {
+        if ascends(Direction::East) {
+            "ascending_east"
+        } else if ascends(Direction::West) {
+            "ascending_west"
+        } else {
+            "east_west"
+        }
+    } else if has(Direction::South) && has(Direction::East) {
+        "south_east"
+    } else if has(Direction::South) && has(Direction::West) {
+        "south_west"
+    } else if has(Direction::North) && has(Direction::West) {
+        "north_west"
+    } else if has(Direction::North) && has(Direction::East) {
+        "north_east"
+    } else if let Some((direction, ascending)) = connections.first() {
+        // A lone neighbour, so the rail ends here and points towards it
+        match (direction, ascending) {
+            (Direction::North, true) => "ascending_north",
+            (Direction::East, true) => "ascending_east",
+            (Direction::South, true) => "ascending_south",
+            (Direction::West, true) => "ascending_west",
+            (Direction::North | Direction::South, false) => "north_south",
+            (Direction::East | Direction::West, false) => "east_west",
+        }
+    } else {
+        "north_south"
+    };
+
+    let mut properties: BlockProperties = BlockProperties::new();
+    properties.insert("shape".to_string(), shape.to_string());
+    properties
+}
+
+/// Doors face out of the wall they are placed in. Doors next to another door
+/// are hinged on the opposite side so they form a double door.
+fn door_properties(world: &WorldToModify, block: Block, x: i32, y: i32, z: i32) -> BlockProperties {
+    let wall_along_x: bool = neighbour(world, x, y, z, Direction::East).is_solid()
+        || neighbour(world, x, y, z, Direction::West).is_solid();
+    let wall_along_z: bool = neighbour(world, x, y, z, Direction::North).is_solid()
+        || neighbour(world, x, y, z, Direction::South).is_solid();
+
+    let facing: Direction = if wall_along_z && !wall_along_x {
+        Direction::East
+    } else {
+        Direction::South
+    };
+
+    let hinge: &str = if is_door(neighbour(world, x, y, z, facing.left())) {
+        "right"
+    } else {
+        "left"
+    };
+
+    let half: &str = if block == Block::DarkOakDoorUpper {
+        "upper"
+    } else {
+        "lower"
+    };
+
+    let mut properties: BlockProperties = BlockProperties::new();
+    properties.insert("facing".to_string(), facing.name().to_string());
+    properties.insert("half".to_string(), half.to_string());
+    properties.insert("hinge".to_string(), hinge.to_string());
+    properties.insert("open".to_string(), "false".to_string());
+    properties.insert("powered".to_string(), "false".to_string());
+    properties
+}
+
+/// Fences and panes connect to their own kind and to solid blocks on each side.
+fn connection_properties(
+    world: &WorldToModify,
+    block: Block,
+    x: i32,
+    y: i32,
+    z: i32,
+) -> BlockProperties {
+    let mut properties: BlockProperties = BlockProperties::new();
+    for direction in Direction::ALL {
+        let connected: bool = connects_to(block, neighbour(world, x, y, z, direction));
+        properties.insert(direction.name().to_string(), connected.to_string());
+    }
+    properties.insert("waterlogged".to_string(), "false".to_string());
+    properties
+}
+
+/// Walls connect like panes and only show their center post at ends, corners and junctions.
+fn wall_properties(world: &WorldToModify, x: i32, y: i32, z: i32) -> BlockProperties {
+    let connected: Vec<bool> = Direction::ALL
+        .iter()
+        .map(|direction: &Direction| {
+            connects_to(
+                Block::CobblestoneWall,
+                neighbour(world, x, y, z, *direction),
+            )
+        })
+        .collect();
+
+    let straight: bool =
+        connected == [true, false, true, false] || connected == [false, true, false, true];
+    let covered: bool = world.get_block(x, y + 1, z).is_some();
+
+    let mut properties: BlockProperties = BlockProperties::new();
+    for (direction, connected) in Direction::ALL.iter().zip(&connected) {
+        let value: &str = if *connected { "low" } else { "none" };
+        properties.insert(direction.name().to_string(), value.to_string());
+    }
+    properties.insert("up".to_string(), (!straight || covered).to_string());
+    properties.insert("waterlogged".to_string(), "false".to_string());
+    properties
+}
+
+/// Stairs climb towards the solid block behind them, or face north when free-standing.
+fn stairs_properties(world: &WorldToModify, x: i32, y: i32, z: i32) -> BlockProperties {
+    let facing: Direction = Direction::ALL
+        .into_iter()
+        .find(|direction: &Direction| {
+            neighbour(world, x, y, z, *direction).is_solid()
+                && !neighbour(world, x, y, z, direction.opposite()).is_solid()
+        })
+        .unwrap_or(Direction::North);
+
+    let mut properties: BlockProperties = BlockProperties::new();
+    properties.insert("facing".to_string(), facing.name().to_string());
+    properties.insert("half".to_string(), "bottom".to_string());
+    properties.insert("shape".to_string(), "straight".to_string());
+    properties.insert("waterlogged".to_string(), "false".to_string());
+    properties
+}
@@ -0,0 +1,111 @@
+use crate::minecraft_version::MinecraftVersion;
+use serde_json::json;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the data pack and namespace of its functions.
+pub const NAMESPACE: &str = "arnis";
+
+/// A named place players can teleport to.
+pub struct Waypoint {
+    pub name: String,
+    /// Fallback for names without any usable characters, e.g. "node_123"
+    pub id: String,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// Writes a data pack to the `datapacks` folder of the world with a teleport function per waypoint,
+/// e.g. `/function arnis:waypoints/central_station`, and `/function arnis:waypoints` listing them all.
+pub fn write_waypoints(
+    world_dir: &Path,
+    waypoints: &[Waypoint],
+    version: MinecraftVersion,
+) -> Result<(), String> {
+    let pack_dir: PathBuf = world_dir.join("datapacks").join(NAMESPACE);
+    if pack_dir.exists() {
+        fs::remove_dir_all(&pack_dir)
+            .map_err(|e| format!("Failed to remove the old waypoint data pack: {}", e))?;
+    }
+
+    let function_dir: PathBuf = pack_dir
+        .join("data")
+        .join(NAMESPACE)
+        .join(version.function_directory());
+    fs::create_dir_all(function_dir.join("waypoints"))
+        .map_err(|e| format!("Failed to create the waypoint data pack: {}", e))?;
+
+    let pack_meta: serde_json::Value = json!({
+        "pack": {
+            "pack_format": version.pack_format(),
+            "description": "Teleports to the named places of the generated area"
+        }
+    });
+    write_file(&pack_dir.join("pack.mcmeta"), &pack_meta.to_string())?;
+
+    let mut function_names: HashSet<String> = HashSet::new();
+    let mut list: Vec<String> = vec![format!(
+        "tellraw @s {}",
+        json!({"text": "Waypoints (click to teleport):", "color": "gold"})
+    )];
+
+    for waypoint in waypoints {
+        let mut function_name: String = function_name(&waypoint.name);
+        if function_name.is_empty() {
+            function_name = waypoint.id.clone();
+        }
+        if !function_names.insert(function_name.clone()) {
+            continue;
+        }
+
+        let teleport: String = format!(
+            "tp @s {} {} {}\ntellraw @s {}\n",
+            f64::from(waypoint.x) + 0.5,
+            waypoint.y,
+            f64::from(waypoint.z) + 0.5,
+            json!({"text": format!("Teleported to {}", waypoint.name), "color": "gray"})
+        );
+        write_file(
+            &function_dir
+                .join("waypoints")
+                .join(format!("{}.mcfunction", function_name)),
+            &teleport,
+        )?;
+
+        let command: String = format!("/function {}:waypoints/{}", NAMESPACE, function_name);
+        list.push(format!(
+            "tellraw @s {}",
+            json!({
+                "text": format!(" - {}", waypoint.name),
+                "color": "aqua",
+                "clickEvent": {"action": "run_command", "value": command},
+                "hoverEvent": {"action": "show_text", "contents": command}
+            })
+        ));
+    }
+
+    write_file(
+        &function_dir.join("waypoints.mcfunction"),
+        &(list.join("\n") + "\n"),
+    )
+}
+
+/// Function names may only contain lowercase letters, digits and underscores.
+fn function_name(name: &str) -> String {
+    let mut function_name: String = String::new();
+    for c in name.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            function_name.push(c);
+        } else if !function_name.is_empty() && !function_name.ends_with('_') {
+            function_name.push('_');
+        }
+    }
+
+    function_name.trim_end_matches('_').to_string()
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
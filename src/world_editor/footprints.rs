@@ -0,0 +1,87 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+/// Where the blocks of a processed OSM element ended up in the world.
+#[derive(Serialize)]
+struct FootprintEntry {
+    #[serde(rename = "type")]
+    kind: String,
+    id: u64,
+    tags: BTreeMap<String, String>,
+    /// Lowest and highest block coordinates as [x, y, z]
+    min: [i32; 3],
+    max: [i32; 3],
+    blocks: u64,
+}
+
+/// Bounding box and count of the blocks placed for the element being generated.
+struct Footprint {
+    min: [i32; 3],
+    max: [i32; 3],
+    blocks: u64,
+}
+
+/// Index from OSM elements to the blocks they produced, for overlays and in-game tooling.
+#[derive(Default)]
+pub struct FootprintIndex {
+    entries: Vec<FootprintEntry>,
+    current: Option<Footprint>,
+}
+
+impl FootprintIndex {
+    /// Starts recording the blocks placed for the next element.
+    pub fn begin(&mut self) {
+        self.current = Some(Footprint {
+            min: [i32::MAX; 3],
+            max: [i32::MIN; 3],
+            blocks: 0,
+        });
+    }
+
+    /// Adds a placed block to the element being generated, if any.
+    pub fn record_block(&mut self, x: i32, y: i32, z: i32) {
+        let Some(footprint) = &mut self.current else {
+            return;
+        };
+
+        for (axis, value) in [x, y, z].into_iter().enumerate() {
+            footprint.min[axis] = footprint.min[axis].min(value);
+            footprint.max[axis] = footprint.max[axis].max(value);
+        }
+        footprint.blocks += 1;
+    }
+
+    /// Stops recording and adds the element to the index if it placed any blocks.
+    pub fn finish(&mut self, kind: &str, id: u64, tags: BTreeMap<String, String>) {
+        let Some(footprint) = self.current.take() else {
+            return;
+        };
+        if footprint.blocks == 0 {
+            return;
+        }
+
+        self.entries.push(FootprintEntry {
+            kind: kind.to_string(),
+            id,
+            tags,
+            min: footprint.min,
+            max: footprint.max,
+            blocks: footprint.blocks,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Writes the index as a JSON array.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let file: File = File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        serde_json::to_writer(BufWriter::new(file), &self.entries)
+            .map_err(|e| format!("Failed to write the footprint index: {}", e))
+    }
+}
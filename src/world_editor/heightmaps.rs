@@ -0,0 +1,111 @@
+use super::{ChunkToModify, SectionToModify, MIN_Y, WORLD_HEIGHT};
+use crate::block_definitions::Block;
+use fastnbt::{LongArray, Value};
+use std::collections::HashMap;
+
+/// The heightmaps Minecraft expects in every fully generated chunk.
+/// Each entry holds the y coordinate above the highest matching block, relative to MIN_Y.
+pub struct ChunkHeightmaps {
+    motion_blocking: [i32; 256],
+    motion_blocking_no_leaves: [i32; 256],
+    ocean_floor: [i32; 256],
+    world_surface: [i32; 256],
+}
+
+impl ChunkHeightmaps {
+    pub fn compute(chunk: &ChunkToModify) -> Self {
+        let mut heightmaps = Self {
+            motion_blocking: [0; 256],
+            motion_blocking_no_leaves: [0; 256],
+            ocean_floor: [0; 256],
+            world_surface: [0; 256],
+        };
+
+        let mut sections: Vec<(&i8, &SectionToModify)> = chunk.sections.iter().collect();
+        sections.sort_by_key(|(y, _)| -**y);
+
+        for x in 0..16u8 {
+            for z in 0..16u8 {
+                let column: usize = usize::from(z) * 16 + usize::from(x);
+
+                'column: for (section_y, section) in &sections {
+                    for y in (0..16u8).rev() {
+                        let block: Block = section.blocks[SectionToModify::index(x, y, z)];
+                        if block == Block::Air {
+                            continue;
+                        }
+
+                        let height: i32 = i32::from(**section_y) * 16 + i32::from(y) + 1 - MIN_Y;
+                        let motion_blocking: bool = block.blocks_motion() || block.is_liquid();
+
+                        Self::raise(&mut heightmaps.world_surface[column], height);
+                        if motion_blocking {
+                            Self::raise(&mut heightmaps.motion_blocking[column], height);
+                        }
+                        if motion_blocking && !block.is_leaves() {
+                            Self::raise(&mut heightmaps.motion_blocking_no_leaves[column], height);
+                        }
+                        if block.blocks_motion() {
+                            Self::raise(&mut heightmaps.ocean_floor[column], height);
+                        }
+
+                        // The remaining heightmaps are at least as high as these two
+                        if heightmaps.ocean_floor[column] != 0
+                            && heightmaps.motion_blocking_no_leaves[column] != 0
+                        {
+                            break 'column;
+                        }
+                    }
+                }
+            }
+        }
+
+        heightmaps
+    }
+
+    /// Only the first (highest) match of a column counts.
+    fn raise(entry: &mut i32, height: i32) {
+        if *entry == 0 {
+            *entry = height;
+        }
+    }
+
+    pub fn to_nbt(&self) -> Value {
+        let mut heightmaps: HashMap<String, Value> = HashMap::new();
+        heightmaps.insert(
+            "MOTION_BLOCKING".to_string(),
+            Self::pack(&self.motion_blocking),
+        );
+        heightmaps.insert(
+            "MOTION_BLOCKING_NO_LEAVES".to_string(),
+            Self::pack(&self.motion_blocking_no_leaves),
+        );
+        heightmaps.insert("OCEAN_FLOOR".to_string(), Self::pack(&self.ocean_floor));
+        heightmaps.insert("WORLD_SURFACE".to_string(), Self::pack(&self.world_surface));
+
+        Value::Compound(heightmaps)
+    }
+
+    /// Packs the 256 column heights into longs. Entries never span two longs.
+    fn pack(heights: &[i32; 256]) -> Value {
+        let mut bits_per_entry: usize = 1;
+        while (1 << bits_per_entry) < WORLD_HEIGHT + 1 {
+            bits_per_entry += 1;
+        }
+        let entries_per_long: usize = 64 / bits_per_entry;
+
+        let data: Vec<i64> = heights
+            .chunks(entries_per_long)
+            .map(|entries: &[i32]| {
+                entries
+                    .iter()
+                    .enumerate()
+                    .fold(0i64, |long: i64, (i, height): (usize, &i32)| {
+                        long | (i64::from(*height) << (i * bits_per_entry))
+                    })
+            })
+            .collect();
+
+        Value::LongArray(LongArray::new(data))
+    }
+}
@@ -0,0 +1,265 @@
+use super::flat_layers;
+use crate::args::Args;
+use crate::block_definitions::Block;
+use crate::level_settings::{GameMode, WorldPreset};
+use fastnbt::Value;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// level.dat of a flat creative 1.21.4 world, the base of every world we create.
+const LEVEL_TEMPLATE: &[u8] = include_bytes!("../../mcassets/level.dat");
+
+/// Writes the level.dat of a new world with the given name.
+pub fn create(world_dir: &Path, level_name: &str) -> Result<(), String> {
+    let mut level: Value = decode(LEVEL_TEMPLATE)?;
+    let data: &mut HashMap<String, Value> = data(&mut level)?;
+
+    data.insert(
+        "LevelName".to_string(),
+        Value::String(level_name.to_string()),
+    );
+    data.insert("LastPlayed".to_string(), Value::Long(now_millis()));
+
+    write(world_dir, &level)
+}
+
+/// Whether the world has nothing in it yet, that is it has no level.dat or no region files.
+/// Must be checked before the region files are written.
+pub fn is_new_world(world_dir: &Path) -> bool {
+    if !world_dir.join("level.dat").exists() {
+        return true;
+    }
+
+    match fs::read_dir(world_dir.join("region")) {
+        Ok(entries) => !entries.flatten().any(|entry: fs::DirEntry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "mca")
+        }),
+        Err(_) => true,
+    }
+}
+
+/// Applies the spawn point and the level settings given on the command line or in the GUI
+/// to the level.dat of the world. Settings that were not given keep their current value.
+/// Worlds without a level.dat get a new one. Existing worlds only get a new spawn point or
+/// generator when one was asked for, and their player stays where it is.
+pub fn update(
+    world_dir: &Path,
+    args: &Args,
+    spawn: (i32, i32, i32),
+    is_new: bool,
+) -> Result<(), String> {
+    let level_path: &Path = &world_dir.join("level.dat");
+    if !level_path.exists() {
+        let level_name: String = world_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Arnis World".to_string());
+        create(world_dir, &level_name)?;
+    }
+
+    let bytes: Vec<u8> =
+        fs::read(level_path).map_err(|e| format!("Failed to read level.dat: {}", e))?;
+    let mut level: Value = decode(&bytes)?;
+    let data: &mut HashMap<String, Value> = data(&mut level)?;
+
+    let (spawn_x, spawn_y, spawn_z) = spawn;
+    let spawn_given: bool =
+        args.spawn_point.is_some() || args.spawn.is_some() || args.spawn_name.is_some();
+    if is_new || spawn_given {
+        data.insert("SpawnX".to_string(), Value::Int(spawn_x));
+        data.insert("SpawnY".to_string(), Value::Int(spawn_y));
+        data.insert("SpawnZ".to_string(), Value::Int(spawn_z));
+    }
+
+    if let Some(difficulty) = args.difficulty {
+        data.insert("Difficulty".to_string(), Value::Byte(difficulty.id() as i8));
+    }
+    if let Some(time) = args.time {
+        data.insert("DayTime".to_string(), Value::Long(time));
+    }
+
+    if let Some(Value::Compound(game_rules)) = data.get_mut("GameRules") {
+        let rules: [(&str, Option<bool>); 3] = [
+            ("doDaylightCycle", args.daylight_cycle),
+            ("doMobSpawning", args.mob_spawning),
+            ("doWeatherCycle", args.weather_cycle),
+        ];
+        for (rule, value) in rules {
+            if let Some(value) = value {
+                game_rules.insert(rule.to_string(), Value::String(value.to_string()));
+            }
+        }
+    }
+
+    // In singleplayer the stored player decides where the world is entered
+    if let Some(Value::Compound(player)) = data.get_mut("Player") {
+        if is_new {
+            player.insert(
+                "Pos".to_string(),
+                Value::List(vec![
+                    Value::Double(f64::from(spawn_x) + 0.5),
+                    Value::Double(f64::from(spawn_y)),
+                    Value::Double(f64::from(spawn_z) + 0.5),
+                ]),
+            );
+        }
+        if let Some(game_mode) = args.gamemode {
+            set_player_game_mode(player, game_mode);
+        }
+    }
+    if let Some(game_mode) = args.gamemode {
+        data.insert("GameType".to_string(), Value::Int(game_mode.id()));
+    }
+
+    if is_new || args.world_preset.is_some() {
+        set_generator(data, args);
+    }
+
+    write(world_dir, &level)
+}
+
+fn set_player_game_mode(player: &mut HashMap<String, Value>, game_mode: GameMode) {
+    player.insert("playerGameType".to_string(), Value::Int(game_mode.id()));
+
+    let creative: bool = game_mode == GameMode::Creative;
+    let can_fly: bool = creative || game_mode == GameMode::Spectator;
+    if let Some(Value::Compound(abilities)) = player.get_mut("abilities") {
+        abilities.insert("instabuild".to_string(), Value::Byte(i8::from(creative)));
+        abilities.insert("mayfly".to_string(), Value::Byte(i8::from(can_fly)));
+        abilities.insert("invulnerable".to_string(), Value::Byte(i8::from(can_fly)));
+        abilities.insert(
+            "flying".to_string(),
+            Value::Byte(i8::from(game_mode == GameMode::Spectator)),
+        );
+        abilities.insert(
+            "mayBuild".to_string(),
+            Value::Byte(i8::from(matches!(
+                game_mode,
+                GameMode::Survival | GameMode::Creative
+            ))),
+        );
+    }
+}
+
+/// Sets the flat generator of the overworld to the chosen preset. Without a preset,
+/// flat worlds have their layers adjusted to the ground level and other generators are left alone.
+fn set_generator(data: &mut HashMap<String, Value>, args: &Args) {
+    let Some(Value::Compound(generator)) = data
+        .get_mut("WorldGenSettings")
+        .and_then(|settings| compound_entry(settings, "dimensions"))
+        .and_then(|dimensions| compound_entry(dimensions, "minecraft:overworld"))
+        .and_then(|overworld| compound_entry(overworld, "generator"))
+    else {
+        return;
+    };
+
+    let is_flat: bool = generator.get("type") == Some(&Value::String("minecraft:flat".to_string()));
+    let preset: WorldPreset = match args.world_preset {
+        Some(preset) => preset,
+        None if is_flat => WorldPreset::Flat,
+        None => return,
+    };
+
+    let (layers, biome): (Vec<(Block, i32)>, &str) = match preset {
+        WorldPreset::Flat => {
+            let biome: &str = if args.winter {
+                "minecraft:snowy_plains"
+            } else {
+                "minecraft:plains"
+            };
+            (flat_layers(args), biome)
+        }
+        WorldPreset::Void => (vec![(Block::Air, 1)], "minecraft:the_void"),
+    };
+
+    let layers: Vec<Value> = layers
+        .into_iter()
+        .map(|(block, height): (Block, i32)| {
+            let mut layer: HashMap<String, Value> = HashMap::new();
+            layer.insert(
+                "block".to_string(),
+                Value::String(block.id_for(args.mc_version)),
+            );
+            layer.insert("height".to_string(), Value::Int(height));
+            Value::Compound(layer)
+        })
+        .collect();
+
+    let mut settings: HashMap<String, Value> = match generator.remove("settings") {
+        Some(Value::Compound(settings)) => settings,
+        _ => HashMap::new(),
+    };
+    settings.insert("layers".to_string(), Value::List(layers));
+    settings.insert("biome".to_string(), Value::String(biome.to_string()));
+    settings.insert("features".to_string(), Value::Byte(0));
+    settings.insert("lakes".to_string(), Value::Byte(0));
+    if preset == WorldPreset::Void {
+        settings.insert("structure_overrides".to_string(), Value::List(vec![]));
+    }
+
+    generator.insert(
+        "type".to_string(),
+        Value::String("minecraft:flat".to_string()),
+    );
+    generator.insert("settings".to_string(), Value::Compound(settings));
+}
+
+fn compound_entry<'a>(value: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    match value {
+        Value::Compound(entries) => entries.get_mut(key),
+        _ => None,
+    }
+}
+
+/// The `Data` compound holding all settings of the level.
+fn data(level: &mut Value) -> Result<&mut HashMap<String, Value>, String> {
+    match level {
+        Value::Compound(root) => match root.get_mut("Data") {
+            Some(Value::Compound(data)) => Ok(data),
+            _ => Err("level.dat has no Data compound".to_string()),
+        },
+        _ => Err("level.dat is not a compound".to_string()),
+    }
+}
+
+fn decode(bytes: &[u8]) -> Result<Value, String> {
+    let mut decoder: GzDecoder<&[u8]> = GzDecoder::new(bytes);
+    let mut decompressed_data: Vec<u8> = Vec::new();
+    decoder
+        .read_to_end(&mut decompressed_data)
+        .map_err(|e| format!("Failed to decompress level.dat: {}", e))?;
+
+    fastnbt::from_bytes(&decompressed_data).map_err(|e| format!("Failed to parse level.dat: {}", e))
+}
+
+fn write(world_dir: &Path, level: &Value) -> Result<(), String> {
+    let serialized_level_data: Vec<u8> =
+        fastnbt::to_bytes(level).map_err(|e| format!("Failed to serialize level.dat: {}", e))?;
+
+    let mut encoder: GzEncoder<Vec<u8>> = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&serialized_level_data)
+        .map_err(|e| format!("Failed to compress level.dat: {}", e))?;
+    let compressed_level_data: Vec<u8> = encoder
+        .finish()
+        .map_err(|e| format!("Failed to finalize compression for level.dat: {}", e))?;
+
+    fs::write(world_dir.join("level.dat"), compressed_level_data)
+        .map_err(|e| format!("Failed to write level.dat: {}", e))
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,505 @@
+use super::{ChunkToModify, SectionToModify, WorldToModify, MIN_Y};
+use crate::block_definitions::Block;
+use fastnbt::ByteArray;
+use fnv::{FnvHashMap, FnvHashSet};
+use std::collections::VecDeque;
+
+const SECTION_VOLUME: usize = 4096;
+
+/// Sky and block light of the modified chunks of a region, spread across chunk borders.
+///
+/// Chunks whose light depends on something that is not known here, such as a modified
+/// chunk in a neighbouring region or a custom block, are marked incomplete and left
+/// for Minecraft to light.
+pub struct RegionLight {
+    chunks: FnvHashMap<(i32, i32), ChunkLight>,
+    incomplete: FnvHashSet<(i32, i32)>,
+}
+
+/// Position of a block within a region: x and z from 0 to 511 and the absolute y.
+type RegionPos = (i32, i32, i32);
+
+impl RegionLight {
+    pub fn compute(world: &WorldToModify, region_x: i32, region_z: i32) -> Self {
+        let mut light = Self {
+            chunks: FnvHashMap::default(),
+            incomplete: FnvHashSet::default(),
+        };
+        let Some(region) = world.regions.get(&(region_x, region_z)) else {
+            return light;
+        };
+
+        for (chunk_pos, chunk) in &region.chunks {
+            if let Some(chunk_light) = ChunkLight::compute(chunk) {
+                light.chunks.insert(*chunk_pos, chunk_light);
+            }
+            // How light passes through custom blocks is unknown
+            if chunk.sections.values().any(|section: &SectionToModify| {
+                section
+                    .blocks
+                    .iter()
+                    .any(|block: &Block| matches!(block, Block::Custom(_)))
+            }) {
+                light.incomplete.insert(*chunk_pos);
+            }
+        }
+
+        // Whether a chunk given relative to this region has blocks written by this run
+        let is_modified = |chunk_x: i32, chunk_z: i32| {
+            let (abs_x, abs_z) = (region_x * 32 + chunk_x, region_z * 32 + chunk_z);
+            world
+                .regions
+                .get(&(abs_x >> 5, abs_z >> 5))
+                .and_then(|region| region.get_chunk(abs_x & 31, abs_z & 31))
+                .is_some_and(|chunk: &ChunkToModify| !chunk.sections.is_empty())
+        };
+
+        let mut sky_queue: VecDeque<RegionPos> = VecDeque::new();
+        let mut block_queue: VecDeque<RegionPos> = VecDeque::new();
+        let chunk_positions: Vec<(i32, i32)> = light.chunks.keys().copied().collect();
+        for (chunk_x, chunk_z) in chunk_positions {
+            for (dx, dz) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let (neighbour_x, neighbour_z) = (chunk_x + dx, chunk_z + dz);
+                let in_region: bool =
+                    (0..32).contains(&neighbour_x) && (0..32).contains(&neighbour_z);
+                if is_modified(neighbour_x, neighbour_z) {
+                    if !in_region {
+                        light.incomplete.insert((chunk_x, chunk_z));
+                    }
+                    continue;
+                }
+
+                // Unmodified neighbours are written without blocks, so the full sky
+                // shines in from their side
+                let chunk_light: &mut ChunkLight =
+                    light.chunks.get_mut(&(chunk_x, chunk_z)).unwrap();
+                for (x, z) in ChunkLight::face(dx, dz) {
+                    for y in 0..chunk_light.height() {
+                        let index: usize = ChunkLight::index(x, y, z);
+                        let level: u8 = 15u8.saturating_sub(chunk_light.opacity[index].max(1));
+                        if level > chunk_light.sky[index] {
+                            chunk_light.sky[index] = level;
+                        }
+                    }
+                }
+            }
+
+            // Every border block may light the neighbouring chunks, and the bottom
+            // layer the unstored sections below
+            let chunk_light: &ChunkLight = &light.chunks[&(chunk_x, chunk_z)];
+            let min_y: i32 = i32::from(chunk_light.min_section) * 16;
+            let columns =
+                (0..16usize).flat_map(|x: usize| (0..16usize).map(move |z: usize| (x, z)));
+            for (x, z) in columns {
+                let is_border: bool = x % 15 == 0 || z % 15 == 0;
+                let height: usize = if is_border { chunk_light.height() } else { 1 };
+                for y in 0..height {
+                    let pos: RegionPos = (
+                        chunk_x * 16 + x as i32,
+                        min_y + y as i32,
+                        chunk_z * 16 + z as i32,
+                    );
+                    let index: usize = ChunkLight::index(x, y, z);
+                    if chunk_light.sky[index] > 1 {
+                        sky_queue.push_back(pos);
+                    }
+                    if chunk_light.block[index] > 1 {
+                        block_queue.push_back(pos);
+                    }
+                }
+            }
+        }
+
+        light.propagate(sky_queue, true);
+        light.propagate(block_queue, false);
+
+        light
+    }
+
+    /// Light of a chunk given relative to the region, if it was modified.
+    pub fn chunk(&self, chunk_x: i32, chunk_z: i32) -> Option<&ChunkLight> {
+        self.chunks.get(&(chunk_x, chunk_z))
+    }
+
+    /// Whether the stored light of the chunk is final, so Minecraft does not need to light it.
+    pub fn is_complete(&self, chunk_x: i32, chunk_z: i32) -> bool {
+        self.chunks.contains_key(&(chunk_x, chunk_z))
+            && !self.incomplete.contains(&(chunk_x, chunk_z))
+    }
+
+    /// Breadth-first light spread between the chunks of the region.
+    fn propagate(&mut self, mut queue: VecDeque<RegionPos>, sky: bool) {
+        while let Some((x, y, z)) = queue.pop_front() {
+            let Some(level) = self.level(x, y, z, sky) else {
+                continue;
+            };
+            if level <= 1 {
+                continue;
+            }
+
+            for (nx, ny, nz) in [
+                (x - 1, y, z),
+                (x + 1, y, z),
+                (x, y - 1, z),
+                (x, y + 1, z),
+                (x, y, z - 1),
+                (x, y, z + 1),
+            ] {
+                if !(0..512).contains(&nx) || !(0..512).contains(&nz) {
+                    continue;
+                }
+                let chunk_pos: (i32, i32) = (nx >> 4, nz >> 4);
+                // Unmodified chunks are lit by Minecraft, which takes their neighbours into account
+                let Some(chunk_light) = self.chunks.get_mut(&chunk_pos) else {
+                    continue;
+                };
+
+                let Some(index) = chunk_light.index_of(nx & 15, ny, nz & 15) else {
+                    // Above the stored sections the sky is fully lit anyway and below
+                    // the world there is nothing to light, anything else cannot be stored
+                    if ny >= MIN_Y && !(sky && ny >= chunk_light.max_y()) {
+                        self.incomplete.insert(chunk_pos);
+                    }
+                    continue;
+                };
+
+                let new_level: u8 = level.saturating_sub(chunk_light.opacity[index].max(1));
+                let levels: &mut Vec<u8> = if sky {
+                    &mut chunk_light.sky
+                } else {
+                    &mut chunk_light.block
+                };
+                if new_level > levels[index] {
+                    levels[index] = new_level;
+                    queue.push_back((nx, ny, nz));
+                }
+            }
+        }
+    }
+
+    fn level(&self, x: i32, y: i32, z: i32, sky: bool) -> Option<u8> {
+        let chunk_light: &ChunkLight = self.chunks.get(&(x >> 4, z >> 4))?;
+        let index: usize = chunk_light.index_of(x & 15, y, z & 15)?;
+        Some(if sky {
+            chunk_light.sky[index]
+        } else {
+            chunk_light.block[index]
+        })
+    }
+}
+
+/// Sky and block light of a single chunk, computed from the blocks placed by the editor.
+/// Light is propagated inside the chunk here, `RegionLight` spreads it across chunk borders.
+pub struct ChunkLight {
+    min_section: i8,
+    max_section: i8,
+    opacity: Vec<u8>,
+    sky: Vec<u8>,
+    block: Vec<u8>,
+}
+
+impl ChunkLight {
+    /// Computes the light of every section between the lowest modified section and
+    /// one section above the highest, so the open sky above the chunk is covered as well.
+    pub fn compute(chunk: &ChunkToModify) -> Option<Self> {
+        let min_section: i8 = *chunk.sections.keys().min()?;
+        let max_section: i8 = chunk.sections.keys().max()?.checked_add(1)?;
+        let height: usize = (max_section - min_section + 1) as usize * 16;
+
+        let mut opacity: Vec<u8> = vec![0; height * 256];
+        let mut emission: Vec<u8> = vec![0; height * 256];
+        for (section_y, section) in &chunk.sections {
+            let offset: usize = (*section_y - min_section) as usize * SECTION_VOLUME;
+            for (index, block) in section.blocks.iter().enumerate() {
+                opacity[offset + index] = block.light_opacity();
+                emission[offset + index] = block.light_emission();
+            }
+        }
+
+        let mut light = Self {
+            min_section,
+            max_section,
+            opacity: vec![],
+            sky: vec![0; height * 256],
+            block: vec![0; height * 256],
+        };
+
+        light.compute_sky_light(&opacity, height);
+        light.compute_block_light(&opacity, &emission, height);
+        light.opacity = opacity;
+
+        Some(light)
+    }
+
+    pub fn min_section(&self) -> i8 {
+        self.min_section
+    }
+
+    pub fn max_section(&self) -> i8 {
+        self.max_section
+    }
+
+    /// Packed SkyLight nibble array of the given section.
+    pub fn sky_light(&self, section_y: i8) -> ByteArray {
+        Self::pack_nibbles(self.section_slice(&self.sky, section_y))
+    }
+
+    /// Packed BlockLight nibble array of the given section.
+    pub fn block_light(&self, section_y: i8) -> ByteArray {
+        Self::pack_nibbles(self.section_slice(&self.block, section_y))
+    }
+
+    fn height(&self) -> usize {
+        (self.max_section - self.min_section + 1) as usize * 16
+    }
+
+    /// Lowest absolute y above the stored sections.
+    fn max_y(&self) -> i32 {
+        (i32::from(self.max_section) + 1) * 16
+    }
+
+    /// Index of a block given by its x and z within the chunk and its absolute y,
+    /// `None` outside the stored sections.
+    fn index_of(&self, x: i32, y: i32, z: i32) -> Option<usize> {
+        let y: i32 = y - i32::from(self.min_section) * 16;
+        if y < 0 || y >= self.height() as i32 {
+            return None;
+        }
+        Some(Self::index(x as usize, y as usize, z as usize))
+    }
+
+    /// Columns of the chunk along the side facing the given direction.
+    fn face(dx: i32, dz: i32) -> impl Iterator<Item = (usize, usize)> {
+        (0..16).map(move |i: usize| match (dx, dz) {
+            (-1, _) => (0, i),
+            (1, _) => (15, i),
+            (_, -1) => (i, 0),
+            _ => (i, 15),
+        })
+    }
+
+    fn section_slice<'a>(&self, levels: &'a [u8], section_y: i8) -> &'a [u8] {
+        let offset: usize = (section_y - self.min_section) as usize * SECTION_VOLUME;
+        &levels[offset..offset + SECTION_VOLUME]
+    }
+
+    fn pack_nibbles(levels: &[u8]) -> ByteArray {
+        let packed: Vec<i8> = levels
+            .chunks(2)
+            .map(|pair: &[u8]| ((pair[0] & 0x0F) | (pair[1] << 4)) as i8)
+            .collect();
+
+        ByteArray::new(packed)
+    }
+
+    fn index(x: usize, y: usize, z: usize) -> usize {
+        y * 256 + z * 16 + x
+    }
+
+    fn compute_sky_light(&mut self, opacity: &[u8], height: usize) {
+        // Full sky light travels straight down until it hits something that absorbs light,
+        // anything dimmer fades by one level per block like regular light
+        for x in 0..16 {
+            for z in 0..16 {
+                let mut level: u8 = 15;
+                for y in (0..height).rev() {
+                    let index: usize = Self::index(x, y, z);
+                    let block_opacity: u8 = opacity[index];
+                    if block_opacity > 0 || level < 15 {
+                        level = level.saturating_sub(block_opacity.max(1));
+                    }
+                    self.sky[index] = level;
+                    if level == 0 {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Only cells next to a darker, non-opaque neighbour need to spread their light
+        let mut queue: VecDeque<(usize, usize, usize)> = VecDeque::new();
+        for y in 0..height {
+            for z in 0..16 {
+                for x in 0..16 {
+                    let level: u8 = self.sky[Self::index(x, y, z)];
+                    if level <= 1 {
+                        continue;
+                    }
+
+                    let has_darker_neighbour: bool =
+                        Self::neighbours(x, y, z, height).any(|(nx, ny, nz)| {
+                            let neighbour: usize = Self::index(nx, ny, nz);
+                            level.saturating_sub(opacity[neighbour].max(1)) > self.sky[neighbour]
+                        });
+                    if has_darker_neighbour {
+                        queue.push_back((x, y, z));
+                    }
+                }
+            }
+        }
+
+        Self::propagate(&mut self.sky, opacity, height, queue);
+    }
+
+    fn compute_block_light(&mut self, opacity: &[u8], emission: &[u8], height: usize) {
+        let mut queue: VecDeque<(usize, usize, usize)> = VecDeque::new();
+
+        for y in 0..height {
+            for z in 0..16 {
+                for x in 0..16 {
+                    let index: usize = Self::index(x, y, z);
+                    if emission[index] > 0 {
+                        self.block[index] = emission[index];
+                        queue.push_back((x, y, z));
+                    }
+                }
+            }
+        }
+
+        Self::propagate(&mut self.block, opacity, height, queue);
+    }
+
+    /// Breadth-first light spread, decreasing the level by at least one per step.
+    fn propagate(
+        levels: &mut [u8],
+        opacity: &[u8],
+        height: usize,
+        mut queue: VecDeque<(usize, usize, usize)>,
+    ) {
+        while let Some((x, y, z)) = queue.pop_front() {
+            let level: u8 = levels[Self::index(x, y, z)];
+            if level <= 1 {
+                continue;
+            }
+
+            for (nx, ny, nz) in Self::neighbours(x, y, z, height) {
+                let neighbour: usize = Self::index(nx, ny, nz);
+                let new_level: u8 = level.saturating_sub(opacity[neighbour].max(1));
+                if new_level > levels[neighbour] {
+                    levels[neighbour] = new_level;
+                    queue.push_back((nx, ny, nz));
+                }
+            }
+        }
+    }
+
+    fn neighbours(
+        x: usize,
+        y: usize,
+        z: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (usize, usize, usize)> {
+        let (x, y, z) = (x as isize, y as isize, z as isize);
+        [
+            (x - 1, y, z),
+            (x + 1, y, z),
+            (x, y - 1, z),
+            (x, y + 1, z),
+            (x, y, z - 1),
+            (x, y, z + 1),
+        ]
+        .into_iter()
+        .filter(move |&(nx, ny, nz)| {
+            (0..16).contains(&nx) && (0..height as isize).contains(&ny) && (0..16).contains(&nz)
+        })
+        .map(|(nx, ny, nz)| (nx as usize, ny as usize, nz as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A block to place: its chunk in region 0,0 and its position within the chunk.
+    type TestBlock = ((i32, i32), (u8, i32, u8), Block);
+
+    fn world_with(blocks: &[TestBlock]) -> WorldToModify {
+        let mut world: WorldToModify = WorldToModify::default();
+        for ((chunk_x, chunk_z), (x, y, z), block) in blocks {
+            world
+                .regions
+                .entry((0, 0))
+                .or_default()
+                .get_or_create_chunk(*chunk_x, *chunk_z)
+                .set_block(*x, *y, *z, *block);
+        }
+        world
+    }
+
+    fn light_at(light: &RegionLight, x: i32, y: i32, z: i32) -> (u8, u8) {
+        (
+            light.level(x, y, z, true).unwrap(),
+            light.level(x, y, z, false).unwrap(),
+        )
+    }
+
+    #[test]
+    fn nibbles_are_packed_low_first() {
+        let levels: Vec<u8> = (0..SECTION_VOLUME).map(|i: usize| (i % 16) as u8).collect();
+        let packed: ByteArray = ChunkLight::pack_nibbles(&levels);
+
+        assert_eq!(packed.len(), 2048);
+        assert_eq!(packed[0] as u8, 0x10);
+        assert_eq!(packed[1] as u8, 0x32);
+        assert_eq!(packed[7] as u8, 0xfe);
+    }
+
+    #[test]
+    fn block_light_crosses_into_modified_neighbour() {
+        // The stones make both chunks store light from the bottom of the world up
+        let world: WorldToModify = world_with(&[
+            ((0, 0), (15, MIN_Y + 24, 8), Block::Glowstone),
+            ((0, 0), (0, MIN_Y, 0), Block::Stone),
+            ((1, 0), (8, MIN_Y, 0), Block::Stone),
+            ((1, 0), (8, MIN_Y + 24, 0), Block::Stone),
+        ]);
+        let light: RegionLight = RegionLight::compute(&world, 0, 0);
+
+        assert_eq!(light_at(&light, 16, MIN_Y + 24, 8).1, 14);
+        assert_eq!(light_at(&light, 18, MIN_Y + 24, 8).1, 12);
+        assert!(light.is_complete(0, 0));
+        assert!(light.is_complete(1, 0));
+    }
+
+    #[test]
+    fn sky_light_enters_from_unmodified_neighbour() {
+        // A roof over the whole chunk, open to the side of the empty chunk next to it
+        let roof: Vec<TestBlock> = (0..16u8)
+            .flat_map(|x: u8| (0..16u8).map(move |z: u8| ((1, 1), (x, 10, z), Block::Stone)))
+            .collect();
+        let world: WorldToModify = world_with(&roof);
+        let light: RegionLight = RegionLight::compute(&world, 0, 0);
+
+        assert_eq!(light_at(&light, 16, 5, 24).0, 14);
+        assert_eq!(light_at(&light, 17, 5, 24).0, 13);
+        assert_eq!(light_at(&light, 24, 11, 24).0, 15);
+    }
+
+    #[test]
+    fn chunks_with_custom_blocks_are_left_to_minecraft() {
+        let lantern: Block = Block::custom("minecraft:lantern[hanging=true]").unwrap();
+        let world: WorldToModify = world_with(&[
+            ((0, 0), (8, MIN_Y, 8), lantern),
+            ((1, 0), (8, MIN_Y, 8), Block::Stone),
+        ]);
+        let light: RegionLight = RegionLight::compute(&world, 0, 0);
+
+        assert!(!light.is_complete(0, 0));
+        assert!(light.is_complete(1, 0));
+    }
+
+    #[test]
+    fn chunks_next_to_other_regions_are_left_to_minecraft() {
+        let mut world: WorldToModify = world_with(&[((31, 0), (8, 0, 8), Block::Stone)]);
+        world
+            .regions
+            .entry((1, 0))
+            .or_default()
+            .get_or_create_chunk(0, 0)
+            .set_block(8, 0, 8, Block::Stone);
+        let light: RegionLight = RegionLight::compute(&world, 0, 0);
+
+        assert!(!light.is_complete(31, 0));
+        assert!(!light.is_complete(0, 0));
+    }
+}
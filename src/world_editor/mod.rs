@@ -0,0 +1,1441 @@
+pub mod bedrock;
+mod biomes;
+pub mod block_entities;
+mod block_states;
+pub mod datapack;
+mod footprints;
+mod heightmaps;
+pub mod level_dat;
+mod lighting;
+mod preview;
+mod schematic;
+
+use crate::args::Args;
+use crate::backup;
+use crate::biome_definitions::Biome;
+use crate::block_definitions::Block;
+use crate::minecraft_version::{MinecraftVersion, MAX_BUILD_HEIGHT, MIN_BUILD_HEIGHT};
+use crate::progress::{emit_gui_preview, emit_gui_progress_update};
+use crate::report::GenerationReport;
+use crate::style::Style;
+use biomes::SectionBiomes;
+use block_entities::{BlockEntity, Entity, Sign};
+use block_states::BlockProperties;
+use colored::Colorize;
+use fastanvil::Region;
+use fastnbt::{ByteArray, IntArray, LongArray, Value};
+use fnv::FnvHashMap;
+use footprints::FootprintIndex;
+use heightmaps::ChunkHeightmaps;
+use indicatif::{ProgressBar, ProgressStyle};
+use lighting::{ChunkLight, RegionLight};
+use schematic::{SchematicFormat, Volume};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+/// File next to the world data listing where each OSM element ended up.
+pub const INDEX_FILE: &str = "osm_index.json";
+
+/// Lowest block y coordinate of the overworld.
+pub const MIN_Y: i32 = MIN_BUILD_HEIGHT;
+/// Number of block layers in the overworld.
+const WORLD_HEIGHT: i32 = MAX_BUILD_HEIGHT - MIN_BUILD_HEIGHT + 1;
+
+/// Blocks of the ground in every column of the area from the bottom up, each with its lowest
+/// and highest y. Without the underground fill only the topsoil and surface are placed.
+pub fn ground_profile(args: &Args) -> Vec<(Block, i32, i32)> {
+    let ground_level: i32 = args.ground_level;
+    let surface: Block = if args.winter {
+        Block::SnowBlock
+    } else {
+        Block::GrassBlock
+    };
+    let lowest_topsoil: i32 = if args.fill_underground {
+        MIN_Y + 1
+    } else {
+        MIN_Y
+    };
+    let topsoil_bottom: i32 = (ground_level - args.topsoil_depth).max(lowest_topsoil);
+
+    let mut profile: Vec<(Block, i32, i32)> = vec![];
+    if args.fill_underground {
+        // Deepslate below y 0 and stone above it, as in generated terrain
+        profile.push((Block::Bedrock, MIN_Y, MIN_Y));
+        profile.push((Block::Deepslate, MIN_Y + 1, (topsoil_bottom - 1).min(-1)));
+        profile.push((Block::Stone, 0, topsoil_bottom - 1));
+    }
+    profile.push((Block::Dirt, topsoil_bottom, ground_level - 1));
+    profile.push((surface, ground_level, ground_level));
+
+    profile.retain(|(_, min_y, max_y)| min_y <= max_y);
+    profile
+}
+
+/// Layers of the flat terrain around the area from the bottom up with their height,
+/// matching the ground of the area.
+pub fn flat_layers(args: &Args) -> Vec<(Block, i32)> {
+    if args.fill_underground {
+        return ground_profile(args)
+            .into_iter()
+            .map(|(block, min_y, max_y)| (block, max_y - min_y + 1))
+            .collect();
+    }
+
+    // Bedrock at the bottom of the world, dirt up to the surface
+    let mut layers: Vec<(Block, i32)> = vec![];
+    if args.ground_level > MIN_Y {
+        layers.push((Block::Bedrock, 1));
+    }
+    if args.ground_level > MIN_Y + 1 {
+        layers.push((Block::Dirt, args.ground_level - MIN_Y - 1));
+    }
+    layers.push((
+        if args.winter {
+            Block::SnowBlock
+        } else {
+            Block::GrassBlock
+        },
+        1,
+    ));
+    layers
+}
+
+/// The block a hanging entity such as an item frame is attached to, if the entity hangs.
+fn hanging_tile(entity: &Value) -> Option<(i32, i32, i32)> {
+    let Value::Compound(entity) = entity else {
+        return None;
+    };
+    match (
+        entity.get("TileX"),
+        entity.get("TileY"),
+        entity.get("TileZ"),
+    ) {
+        (Some(Value::Int(x)), Some(Value::Int(y)), Some(Value::Int(z))) => Some((*x, *y, *z)),
+        _ => None,
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Chunk {
+    sections: Vec<Section>,
+    x_pos: i32,
+    z_pos: i32,
+    #[serde(default)]
+    is_light_on: u8,
+    #[serde(flatten)]
+    other: FnvHashMap<String, Value>,
+}
+
+impl Chunk {
+    /// A fully generated chunk without any blocks, so the game does not generate terrain
+    /// in it. Every section of the world height is present and carries the given biomes.
+    fn empty(x_pos: i32, z_pos: i32, version: MinecraftVersion, biomes: &[Biome; 16]) -> Self {
+        let sections: Vec<Section> = (MIN_Y >> 4..(MIN_Y + WORLD_HEIGHT) >> 4)
+            .map(|y: i32| Section::empty(y as i8, biomes))
+            .collect();
+
+        let mut other: FnvHashMap<String, Value> = FnvHashMap::default();
+        other.insert(
+            "DataVersion".to_string(),
+            Value::Int(version.data_version()),
+        );
+        other.insert("yPos".to_string(), Value::Int(MIN_Y >> 4));
+        other.insert(
+            "Status".to_string(),
+            Value::String("minecraft:full".to_string()),
+        );
+        other.insert("LastUpdate".to_string(), Value::Long(0));
+        other.insert("InhabitedTime".to_string(), Value::Long(0));
+        other.insert("block_entities".to_string(), Value::List(vec![]));
+        other.insert("block_ticks".to_string(), Value::List(vec![]));
+        other.insert("fluid_ticks".to_string(), Value::List(vec![]));
+        other.insert(
+            "PostProcessing".to_string(),
+            Value::List(vec![Value::List(vec![]); sections.len()]),
+        );
+
+        let mut structures: HashMap<String, Value> = HashMap::new();
+        structures.insert("References".to_string(), Value::Compound(HashMap::new()));
+        structures.insert("starts".to_string(), Value::Compound(HashMap::new()));
+        other.insert("structures".to_string(), Value::Compound(structures));
+
+        Self {
+            sections,
+            x_pos,
+            z_pos,
+            is_light_on: 0,
+            other,
+        }
+    }
+
+    /// Replaces the sections at the heights of the given ones.
+    fn replace_sections(&mut self, sections: Vec<Section>) {
+        for section in sections {
+            if let Some(existing) = self
+                .sections
+                .iter_mut()
+                .find(|existing: &&mut Section| existing.y == section.y)
+            {
+                *existing = section;
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Section {
+    block_states: Blockstates,
+    #[serde(rename = "Y")]
+    y: i8,
+    #[serde(rename = "SkyLight", default, skip_serializing_if = "Option::is_none")]
+    sky_light: Option<ByteArray>,
+    #[serde(
+        rename = "BlockLight",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    block_light: Option<ByteArray>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    biomes: Option<SectionBiomes>,
+    #[serde(flatten)]
+    other: FnvHashMap<String, Value>,
+}
+
+impl Section {
+    /// A section filled with air.
+    fn empty(y: i8, biomes: &[Biome; 16]) -> Self {
+        Self {
+            block_states: Blockstates {
+                palette: vec![PaletteItem {
+                    name: Block::Air.name().to_string(),
+                    properties: None,
+                }],
+                data: None,
+                other: FnvHashMap::default(),
+            },
+            y,
+            sky_light: None,
+            block_light: None,
+            biomes: Some(SectionBiomes::from_columns(biomes)),
+            other: FnvHashMap::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Blockstates {
+    palette: Vec<PaletteItem>,
+    data: Option<LongArray>,
+    #[serde(flatten)]
+    other: FnvHashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PaletteItem {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Properties")]
+    properties: Option<Value>,
+}
+
+struct SectionToModify {
+    blocks: [Block; 4096],
+    /// State properties computed from neighbouring blocks, keyed by block index
+    properties: FnvHashMap<usize, BlockProperties>,
+}
+
+impl SectionToModify {
+    fn get_block(&self, x: u8, y: u8, z: u8) -> Option<Block> {
+        let b = self.blocks[Self::index(x, y, z)];
+        if b == Block::Air {
+            return None;
+        }
+
+        Some(b)
+    }
+
+    fn set_block(&mut self, x: u8, y: u8, z: u8, block: Block) {
+        let index: usize = Self::index(x, y, z);
+        self.blocks[index] = block;
+        self.properties.remove(&index);
+    }
+
+    fn set_properties(&mut self, x: u8, y: u8, z: u8, properties: BlockProperties) {
+        self.properties.insert(Self::index(x, y, z), properties);
+    }
+
+    /// The block at the given index together with its computed properties.
+    fn state_at(&self, index: usize) -> (Block, Option<&BlockProperties>) {
+        (self.blocks[index], self.properties.get(&index))
+    }
+
+    fn index(x: u8, y: u8, z: u8) -> usize {
+        usize::from(y) % 16 * 256 + usize::from(z) * 16 + usize::from(x)
+    }
+
+    fn to_section(&self, y: i8, version: MinecraftVersion) -> Section {
+        // Blocks missing from the target version are written as their substitutes
+        let state = |i: usize| {
+            let (block, properties) = self.state_at(i);
+            (block.for_version(version), properties)
+        };
+
+        let mut palette: Vec<(Block, Option<&BlockProperties>)> =
+            (0..self.blocks.len()).map(state).collect();
+        palette.sort();
+        palette.dedup();
+
+        let palette_lookup: FnvHashMap<_, _> = palette
+            .iter()
+            .enumerate()
+            .map(|(k, v)| (v, i64::try_from(k).unwrap()))
+            .collect();
+
+        let mut bits_per_block = 4; // minimum allowed
+        while (1 << bits_per_block) < palette.len() {
+            bits_per_block += 1;
+        }
+
+        let mut data = vec![];
+
+        let mut cur = 0;
+        let mut cur_idx = 0;
+        for i in 0..self.blocks.len() {
+            let p = palette_lookup[&state(i)];
+
+            if cur_idx + bits_per_block > 64 {
+                data.push(cur);
+                cur = 0;
+                cur_idx = 0;
+            }
+
+            cur |= p << cur_idx;
+            cur_idx += bits_per_block;
+        }
+
+        if cur_idx > 0 {
+            data.push(cur);
+        }
+
+        let palette = palette
+            .iter()
+            .map(|(block, properties)| PaletteItem {
+                name: block.id_for(version),
+                properties: Self::merge_properties(*block, *properties),
+            })
+            .collect();
+
+        Section {
+            block_states: Blockstates {
+                palette,
+                data: Some(LongArray::new(data)),
+                other: FnvHashMap::default(),
+            },
+            y,
+            sky_light: None,
+            block_light: None,
+            biomes: None,
+            other: FnvHashMap::default(),
+        }
+    }
+}
+
+impl SectionToModify {
+    /// Combines the fixed properties of a block with those computed from its neighbours.
+    fn merge_properties(block: Block, computed: Option<&BlockProperties>) -> Option<Value> {
+        let Some(computed) = computed else {
+            return block.properties();
+        };
+
+        let mut properties: HashMap<String, Value> = match block.properties() {
+            Some(Value::Compound(map)) => map,
+            _ => HashMap::new(),
+        };
+        for (key, value) in computed {
+            properties.insert(key.clone(), Value::String(value.clone()));
+        }
+
+        Some(Value::Compound(properties))
+    }
+
+    /// All state properties of a block as plain strings, both the fixed ones
+    /// and those computed from neighbours.
+    fn state_properties(
+        block: Block,
+        computed: Option<&BlockProperties>,
+    ) -> BTreeMap<String, String> {
+        match Self::merge_properties(block, computed) {
+            Some(Value::Compound(properties)) => properties
+                .into_iter()
+                .filter_map(|(key, value)| match value {
+                    Value::String(value) => Some((key, value)),
+                    _ => None,
+                })
+                .collect(),
+            _ => BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for SectionToModify {
+    fn default() -> Self {
+        Self {
+            blocks: [Block::Air; 4096],
+            properties: FnvHashMap::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ChunkToModify {
+    sections: FnvHashMap<i8, SectionToModify>,
+    biomes: [Option<Biome>; 16],
+    /// Block entities keyed by their position within the chunk
+    block_entities: FnvHashMap<(u8, i32, u8), BlockEntity>,
+    /// Entities keyed by the block position they occupy within the chunk
+    entities: FnvHashMap<(u8, i32, u8), Entity>,
+}
+
+impl ChunkToModify {
+    fn get_block(&self, x: u8, y: i32, z: u8) -> Option<Block> {
+        let section_idx: i8 = (y >> 4).try_into().ok()?;
+
+        let section = self.sections.get(&section_idx)?;
+
+        section.get_block(x, (y & 15).try_into().unwrap(), z)
+    }
+
+    fn set_block(&mut self, x: u8, y: i32, z: u8, block: Block) {
+        let Ok(section_idx) = i8::try_from(y >> 4) else {
+            return;
+        };
+
+        let section = self.sections.entry(section_idx).or_default();
+
+        section.set_block(x, (y & 15).try_into().unwrap(), z, block);
+
+        // Whatever was attached to the replaced block goes with it
+        self.block_entities.remove(&(x, y, z));
+        self.entities.remove(&(x, y, z));
+    }
+
+    fn block_entities_nbt(
+        &self,
+        chunk_x: i32,
+        chunk_z: i32,
+        version: MinecraftVersion,
+    ) -> Vec<Value> {
+        self.block_entities
+            .iter()
+            .map(|((x, y, z), entity)| {
+                entity.to_nbt(
+                    chunk_x * 16 + i32::from(*x),
+                    *y,
+                    chunk_z * 16 + i32::from(*z),
+                    version,
+                )
+            })
+            .collect()
+    }
+
+    fn entities_nbt(&self, chunk_x: i32, chunk_z: i32, version: MinecraftVersion) -> Vec<Value> {
+        self.entities
+            .iter()
+            .map(|((x, y, z), entity)| {
+                entity.to_nbt(
+                    chunk_x * 16 + i32::from(*x),
+                    *y,
+                    chunk_z * 16 + i32::from(*z),
+                    version,
+                )
+            })
+            .collect()
+    }
+
+    fn set_properties(&mut self, x: u8, y: i32, z: u8, properties: BlockProperties) {
+        let Ok(section_idx) = i8::try_from(y >> 4) else {
+            return;
+        };
+
+        if let Some(section) = self.sections.get_mut(&section_idx) {
+            section.set_properties(x, (y & 15).try_into().unwrap(), z, properties);
+        }
+    }
+
+    fn set_biome(&mut self, x: u8, z: u8, biome: Biome) {
+        self.biomes[usize::from(z / 4) * 4 + usize::from(x / 4)] = Some(biome);
+    }
+
+    /// Biome of each 4x4 column of the chunk, falling back to plains where nothing was set.
+    fn biome_columns(&self, winter: bool) -> [Biome; 16] {
+        self.biomes.map(|biome: Option<Biome>| {
+            let biome: Biome = biome.unwrap_or(Biome::Plains);
+            if winter {
+                biome.winter_variant()
+            } else {
+                biome
+            }
+        })
+    }
+
+    /// Builds the sections of the chunk together with their precomputed light and biomes.
+    /// Empty sections in between and the one above the highest block are included
+    /// so that light is stored for the whole lit volume.
+    fn sections(
+        &self,
+        light: Option<&ChunkLight>,
+        winter: bool,
+        version: MinecraftVersion,
+    ) -> Vec<Section> {
+        let Some(light) = light else {
+            return vec![];
+        };
+
+        let empty_section: SectionToModify = SectionToModify::default();
+        let biome_columns: [Biome; 16] = self.biome_columns(winter);
+        (light.min_section()..=light.max_section())
+            .map(|y: i8| {
+                let mut section: Section = self
+                    .sections
+                    .get(&y)
+                    .unwrap_or(&empty_section)
+                    .to_section(y, version);
+                section.sky_light = Some(light.sky_light(y));
+                section.block_light = Some(light.block_light(y));
+                section.biomes = Some(SectionBiomes::from_columns(&biome_columns));
+                section
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct RegionToModify {
+    chunks: FnvHashMap<(i32, i32), ChunkToModify>,
+}
+
+impl RegionToModify {
+    fn get_or_create_chunk(&mut self, x: i32, z: i32) -> &mut ChunkToModify {
+        self.chunks.entry((x, z)).or_default()
+    }
+
+    fn get_chunk(&self, x: i32, z: i32) -> Option<&ChunkToModify> {
+        self.chunks.get(&(x, z))
+    }
+}
+
+#[derive(Default)]
+struct WorldToModify {
+    regions: FnvHashMap<(i32, i32), RegionToModify>,
+}
+
+impl WorldToModify {
+    fn get_or_create_region(&mut self, x: i32, z: i32) -> &mut RegionToModify {
+        self.regions.entry((x, z)).or_default()
+    }
+
+    fn get_region(&self, x: i32, z: i32) -> Option<&RegionToModify> {
+        self.regions.get(&(x, z))
+    }
+
+    fn get_block(&self, x: i32, y: i32, z: i32) -> Option<Block> {
+        let chunk_x: i32 = x >> 4;
+        let chunk_z: i32 = z >> 4;
+        let region_x: i32 = chunk_x >> 5;
+        let region_z: i32 = chunk_z >> 5;
+
+        let region: &RegionToModify = self.get_region(region_x, region_z)?;
+        let chunk: &ChunkToModify = region.get_chunk(chunk_x & 31, chunk_z & 31)?;
+
+        chunk.get_block(
+            (x & 15).try_into().unwrap(),
+            y,
+            (z & 15).try_into().unwrap(),
+        )
+    }
+
+    /// The block at the given position together with its computed properties, air if unset.
+    fn get_state(&self, x: i32, y: i32, z: i32) -> (Block, Option<&BlockProperties>) {
+        let chunk: Option<&ChunkToModify> = self
+            .get_region(x >> 9, z >> 9)
+            .and_then(|region: &RegionToModify| region.get_chunk((x >> 4) & 31, (z >> 4) & 31));
+        let section: Option<&SectionToModify> = chunk.and_then(|chunk: &ChunkToModify| {
+            let section_idx: i8 = i8::try_from(y >> 4).ok()?;
+            chunk.sections.get(&section_idx)
+        });
+
+        match section {
+            Some(section) => section.state_at(SectionToModify::index(
+                (x & 15) as u8,
+                (y & 15) as u8,
+                (z & 15) as u8,
+            )),
+            None => (Block::Air, None),
+        }
+    }
+
+    /// Lowest and highest corner of the box enclosing every placed block.
+    #[allow(clippy::type_complexity)]
+    fn bounds(&self) -> Option<((i32, i32, i32), (i32, i32, i32))> {
+        let mut bounds: Option<((i32, i32, i32), (i32, i32, i32))> = None;
+
+        for ((region_x, region_z), region) in &self.regions {
+            for ((chunk_x, chunk_z), chunk) in &region.chunks {
+                for (section_y, section) in &chunk.sections {
+                    for (index, block) in section.blocks.iter().enumerate() {
+                        if *block == Block::Air {
+                            continue;
+                        }
+
+                        let x: i32 = (region_x * 32 + chunk_x) * 16 + (index % 16) as i32;
+                        let y: i32 = i32::from(*section_y) * 16 + (index / 256) as i32;
+                        let z: i32 = (region_z * 32 + chunk_z) * 16 + (index / 16 % 16) as i32;
+
+                        bounds = Some(match bounds {
+                            Some((min, max)) => (
+                                (min.0.min(x), min.1.min(y), min.2.min(z)),
+                                (max.0.max(x), max.1.max(y), max.2.max(z)),
+                            ),
+                            None => ((x, y, z), (x, y, z)),
+                        });
+                    }
+                }
+            }
+        }
+
+        bounds
+    }
+
+    /// Y coordinate of the highest block placed in the given column.
+    fn highest_block(&self, x: i32, z: i32) -> Option<i32> {
+        let chunk: &ChunkToModify = self
+            .get_region(x >> 9, z >> 9)?
+            .get_chunk((x >> 4) & 31, (z >> 4) & 31)?;
+
+        let mut sections: Vec<(&i8, &SectionToModify)> = chunk.sections.iter().collect();
+        sections.sort_by_key(|(y, _)| -**y);
+        sections.into_iter().find_map(|(section_y, section)| {
+            (0..16u8).rev().find_map(|y: u8| {
+                section
+                    .get_block((x & 15) as u8, y, (z & 15) as u8)
+                    .map(|_| i32::from(*section_y) * 16 + i32::from(y))
+            })
+        })
+    }
+
+    /// Number of blocks of each type placed in the world.
+    fn block_counts(&self) -> HashMap<Block, u64> {
+        let mut block_counts: HashMap<Block, u64> = HashMap::new();
+        for block in self
+            .regions
+            .values()
+            .flat_map(|region: &RegionToModify| region.chunks.values())
+            .flat_map(|chunk: &ChunkToModify| chunk.sections.values())
+            .flat_map(|section: &SectionToModify| section.blocks.iter())
+            .filter(|block: &&Block| **block != Block::Air)
+        {
+            *block_counts.entry(*block).or_default() += 1;
+        }
+
+        block_counts
+    }
+
+    /// Lowest height in the column above the ground where a player stands on a block
+    /// with two free blocks above, or the top of the column if there is none.
+    fn standing_height(&self, x: i32, z: i32, ground_level: i32) -> i32 {
+        let top: i32 = self.highest_block(x, z).unwrap_or(ground_level);
+        let is_free = |y: i32| -> bool {
+            self.get_block(x, y, z)
+                .is_none_or(|block: Block| !block.blocks_motion() && !block.is_liquid())
+        };
+        let is_floor = |y: i32| -> bool {
+            self.get_block(x, y, z)
+                .is_some_and(|block: Block| block.blocks_motion())
+        };
+
+        (ground_level + 1..=top)
+            .find(|&y: &i32| is_floor(y - 1) && is_free(y) && is_free(y + 1))
+            .unwrap_or(top + 1)
+    }
+
+    /// Placed blocks that do not exist in the given version and are written as a substitute.
+    fn unavailable_blocks(&self, version: MinecraftVersion) -> BTreeSet<Block> {
+        self.regions
+            .values()
+            .flat_map(|region: &RegionToModify| region.chunks.values())
+            .flat_map(|chunk: &ChunkToModify| chunk.sections.values())
+            .flat_map(|section: &SectionToModify| section.blocks.iter().copied())
+            .filter(|block: &Block| block.for_version(version) != *block)
+            .collect()
+    }
+
+    /// NBT of every block entity in the world, keyed by world position.
+    fn block_entities(&self, version: MinecraftVersion) -> Vec<((i32, i32, i32), Value)> {
+        let mut block_entities: Vec<((i32, i32, i32), Value)> = vec![];
+
+        for ((region_x, region_z), region) in &self.regions {
+            for ((chunk_x, chunk_z), chunk) in &region.chunks {
+                for ((x, y, z), entity) in &chunk.block_entities {
+                    let x: i32 = (region_x * 32 + chunk_x) * 16 + i32::from(*x);
+                    let z: i32 = (region_z * 32 + chunk_z) * 16 + i32::from(*z);
+                    block_entities.push(((x, *y, z), entity.to_nbt(x, *y, z, version)));
+                }
+            }
+        }
+
+        block_entities
+    }
+
+    fn set_block(&mut self, x: i32, y: i32, z: i32, block: Block) {
+        let chunk_x: i32 = x >> 4;
+        let chunk_z: i32 = z >> 4;
+        let region_x: i32 = chunk_x >> 5;
+        let region_z: i32 = chunk_z >> 5;
+
+        let region: &mut RegionToModify = self.get_or_create_region(region_x, region_z);
+        let chunk: &mut ChunkToModify = region.get_or_create_chunk(chunk_x & 31, chunk_z & 31);
+
+        chunk.set_block(
+            (x & 15).try_into().unwrap(),
+            y,
+            (z & 15).try_into().unwrap(),
+            block,
+        );
+    }
+
+    fn set_properties(&mut self, x: i32, y: i32, z: i32, properties: BlockProperties) {
+        let chunk_x: i32 = x >> 4;
+        let chunk_z: i32 = z >> 4;
+        let region_x: i32 = chunk_x >> 5;
+        let region_z: i32 = chunk_z >> 5;
+
+        let region: &mut RegionToModify = self.get_or_create_region(region_x, region_z);
+        let chunk: &mut ChunkToModify = region.get_or_create_chunk(chunk_x & 31, chunk_z & 31);
+
+        chunk.set_properties(
+            (x & 15).try_into().unwrap(),
+            y,
+            (z & 15).try_into().unwrap(),
+            properties,
+        );
+    }
+
+    fn get_or_create_chunk(&mut self, x: i32, z: i32) -> &mut ChunkToModify {
+        let chunk_x: i32 = x >> 4;
+        let chunk_z: i32 = z >> 4;
+        let region_x: i32 = chunk_x >> 5;
+        let region_z: i32 = chunk_z >> 5;
+
+        let region: &mut RegionToModify = self.get_or_create_region(region_x, region_z);
+        region.get_or_create_chunk(chunk_x & 31, chunk_z & 31)
+    }
+
+    fn set_block_entity(&mut self, x: i32, y: i32, z: i32, entity: BlockEntity) {
+        let chunk: &mut ChunkToModify = self.get_or_create_chunk(x, z);
+        chunk.block_entities.insert(
+            (
+                (x & 15).try_into().unwrap(),
+                y,
+                (z & 15).try_into().unwrap(),
+            ),
+            entity,
+        );
+    }
+
+    #[allow(dead_code)]
+    fn set_entity(&mut self, x: i32, y: i32, z: i32, entity: Entity) {
+        let chunk: &mut ChunkToModify = self.get_or_create_chunk(x, z);
+        chunk.entities.insert(
+            (
+                (x & 15).try_into().unwrap(),
+                y,
+                (z & 15).try_into().unwrap(),
+            ),
+            entity,
+        );
+    }
+
+    fn set_biome(&mut self, x: i32, z: i32, biome: Biome) {
+        let chunk_x: i32 = x >> 4;
+        let chunk_z: i32 = z >> 4;
+        let region_x: i32 = chunk_x >> 5;
+        let region_z: i32 = chunk_z >> 5;
+
+        let region: &mut RegionToModify = self.get_or_create_region(region_x, region_z);
+        let chunk: &mut ChunkToModify = region.get_or_create_chunk(chunk_x & 31, chunk_z & 31);
+
+        chunk.set_biome(
+            (x & 15).try_into().unwrap(),
+            (z & 15).try_into().unwrap(),
+            biome,
+        );
+    }
+}
+
+pub struct WorldEditor<'a> {
+    region_dir: String,
+    world: WorldToModify,
+    scale_factor_x: f64,
+    scale_factor_z: f64,
+    args: &'a Args,
+    report: GenerationReport,
+    footprints: FootprintIndex,
+    style: Style,
+}
+
+impl<'a> WorldEditor<'a> {
+    /// Initializes the WorldEditor with the region directory.
+    pub fn new(
+        region_dir: &str,
+        scale_factor_x: f64,
+        scale_factor_z: f64,
+        args: &'a Args,
+        style: Style,
+    ) -> Self {
+        Self {
+            region_dir: region_dir.to_string(),
+            world: WorldToModify::default(),
+            scale_factor_x,
+            scale_factor_z,
+            args,
+            report: GenerationReport::default(),
+            footprints: FootprintIndex::default(),
+            style,
+        }
+    }
+
+    /// Creates an empty region file for the given region coordinates.
+    fn create_region(&self, region_x: i32, region_z: i32) -> Region<File> {
+        let out_path: String = format!("{}/r.{}.{}.mca", self.region_dir, region_x, region_z);
+
+        let region_file: File = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&out_path)
+            .expect("Failed to open region file");
+
+        Region::new(region_file).expect("Failed to create region")
+    }
+
+    /// Writes the entities of a region to its file in the `entities` directory next to
+    /// the region directory. Regions without entities get no file. Entities already in the
+    /// file are kept, except hanging ones at a block where a new one is placed.
+    fn save_entities(&self, region_x: i32, region_z: i32, region_to_modify: &RegionToModify) {
+        if region_to_modify
+            .chunks
+            .values()
+            .all(|chunk: &ChunkToModify| chunk.entities.is_empty())
+        {
+            return;
+        }
+
+        let entities_dir: PathBuf = Path::new(&self.region_dir)
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join("entities");
+        fs::create_dir_all(&entities_dir).expect("Failed to create entities directory");
+
+        let region_path: PathBuf = entities_dir.join(format!("r.{}.{}.mca", region_x, region_z));
+        let existing: bool = fs::metadata(&region_path).is_ok_and(|meta| meta.len() > 0);
+        let region_file: File = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&region_path)
+            .expect("Failed to open entities region file");
+        let mut region: Region<File> = if existing {
+            Region::from_stream(region_file).expect("Failed to read entities region")
+        } else {
+            Region::new(region_file).expect("Failed to create region")
+        };
+
+        for ((chunk_x, chunk_z), chunk_to_modify) in &region_to_modify.chunks {
+            if chunk_to_modify.entities.is_empty() {
+                continue;
+            }
+
+            let chunk_x_abs: i32 = chunk_x + region_x * 32;
+            let chunk_z_abs: i32 = chunk_z + region_z * 32;
+
+            let mut entities: Vec<Value> =
+                chunk_to_modify.entities_nbt(chunk_x_abs, chunk_z_abs, self.args.mc_version);
+
+            // Keep what the chunk already held, dropping entities replaced by ours
+            let existing_chunk: Option<HashMap<String, Value>> = region
+                .read_chunk(*chunk_x as usize, *chunk_z as usize)
+                .ok()
+                .flatten()
+                .and_then(|data: Vec<u8>| fastnbt::from_bytes(&data).ok());
+            if let Some(Value::List(old_entities)) = existing_chunk
+                .and_then(|mut chunk: HashMap<String, Value>| chunk.remove("Entities"))
+            {
+                let new_tiles: Vec<Option<(i32, i32, i32)>> =
+                    entities.iter().map(hanging_tile).collect();
+                entities.extend(old_entities.into_iter().filter(|entity: &Value| {
+                    hanging_tile(entity).is_none_or(|tile| !new_tiles.contains(&Some(tile)))
+                }));
+            }
+
+            let mut chunk: HashMap<String, Value> = HashMap::new();
+            chunk.insert(
+                "DataVersion".to_string(),
+                Value::Int(self.args.mc_version.data_version()),
+            );
+            chunk.insert(
+                "Position".to_string(),
+                Value::IntArray(IntArray::new(vec![chunk_x_abs, chunk_z_abs])),
+            );
+            chunk.insert("Entities".to_string(), Value::List(entities));
+
+            let ser: Vec<u8> = fastnbt::to_bytes(&Value::Compound(chunk)).unwrap();
+            region
+                .write_chunk(*chunk_x as usize, *chunk_z as usize, &ser)
+                .unwrap();
+        }
+    }
+
+    pub fn get_max_coords(&self) -> (i32, i32) {
+        (self.scale_factor_x as i32, self.scale_factor_x as i32)
+    }
+
+    /// Notes in the generation report that the element being generated was left out
+    /// or generated with defaults, and why.
+    pub fn report_issue(&mut self, reason: &str) {
+        self.report.issue(reason);
+    }
+
+    pub fn report_mut(&mut self) -> &mut GenerationReport {
+        &mut self.report
+    }
+
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    pub fn footprints(&self) -> &FootprintIndex {
+        &self.footprints
+    }
+
+    pub fn footprints_mut(&mut self) -> &mut FootprintIndex {
+        &mut self.footprints
+    }
+
+    /// Height in blocks of something the given number of blocks high at a vertical scale of 1,
+    /// at least one block unless it is flat.
+    pub fn scale_height(&self, height: i32) -> i32 {
+        let scaled: i32 = (f64::from(height) * self.args.vertical_scale()).round() as i32;
+        if scaled == 0 {
+            height.signum()
+        } else {
+            scaled
+        }
+    }
+
+    /// Width in blocks of something the given number of blocks wide at a scale of 1,
+    /// at least one block unless it has no width.
+    pub fn scale_width(&self, width: i32) -> i32 {
+        let scaled: i32 = (f64::from(width) * self.args.scale).round() as i32;
+        if scaled == 0 {
+            width.signum()
+        } else {
+            scaled
+        }
+    }
+
+    /// Height at which a player can stand in the given column.
+    pub fn standing_height(&self, x: i32, z: i32) -> i32 {
+        self.world.standing_height(x, z, self.args.ground_level)
+    }
+
+    // Unused and not tested
+    /*pub fn block_at(&self, x: i32, y: i32, z: i32) -> bool {
+        self.world.get_block(x, y, z).is_some()
+    }*/
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_sign(
+        &mut self,
+        line1: String,
+        line2: String,
+        line3: String,
+        line4: String,
+        x: i32,
+        y: i32,
+        z: i32,
+        rotation: i8,
+    ) {
+        self.set_block_entity(
+            Sign::new([line1, line2, line3, line4]).rotation(rotation),
+            x,
+            y,
+            z,
+        );
+    }
+
+    /// Places a block entity together with its block. An entity already at the position
+    /// is replaced. Nothing is placed if another block occupies the position.
+    pub fn set_block_entity(&mut self, entity: impl Into<BlockEntity>, x: i32, y: i32, z: i32) {
+        let entity: BlockEntity = entity.into().for_version(self.args.mc_version);
+        let block: Block = entity.block();
+
+        self.set_block(block, x, y, z, Some(&[block]), None);
+
+        if self.world.get_block(x, y, z) == Some(block) {
+            if let Some(properties) = entity.properties() {
+                self.world.set_properties(x, y, z, properties);
+            }
+            self.world.set_block_entity(x, y, z, entity);
+        }
+    }
+
+    /// Places an entity such as an item frame in the given block space,
+    /// replacing any entity previously placed there.
+    #[allow(dead_code)]
+    pub fn set_entity(&mut self, entity: impl Into<Entity>, x: i32, y: i32, z: i32) {
+        // Check if coordinates are within bounds
+        if x < 0 || x > self.scale_factor_x as i32 || z < 0 || z > self.scale_factor_z as i32 {
+            return;
+        }
+
+        if !self.within_build_height(y) {
+            return;
+        }
+
+        self.world.set_entity(x, y, z, entity.into());
+    }
+
+    /// Sets a block of the specified type at the given coordinates.
+    pub fn set_block(
+        &mut self,
+        block: Block,
+        x: i32,
+        y: i32,
+        z: i32,
+        override_whitelist: Option<&[Block]>,
+        override_blacklist: Option<&[Block]>,
+    ) {
+        // Check if coordinates are within bounds
+        if x < 0 || x > self.scale_factor_x as i32 || z < 0 || z > self.scale_factor_z as i32 {
+            return;
+        }
+
+        // Blocks outside the build height of the version are left out
+        if !self.within_build_height(y) {
+            return;
+        }
+
+        let should_insert = if let Some(existing_block) = self.world.get_block(x, y, z) {
+            // Check against whitelist and blacklist
+            if let Some(whitelist) = override_whitelist {
+                whitelist
+                    .iter()
+                    .any(|whitelisted_block: &Block| *whitelisted_block == existing_block)
+            } else if let Some(blacklist) = override_blacklist {
+                !blacklist
+                    .iter()
+                    .any(|blacklisted_block: &Block| *blacklisted_block == existing_block)
+            } else {
+                false
+            }
+        } else {
+            true
+        };
+
+        if should_insert {
+            self.world.set_block(x, y, z, block);
+            if block != Block::Air {
+                self.footprints.record_block(x, y, z);
+            }
+        }
+    }
+
+    /// Whether blocks can be placed at the given height in the target version,
+    /// noting in the generation report when the element being generated reaches past it.
+    fn within_build_height(&mut self, y: i32) -> bool {
+        let version: MinecraftVersion = self.args.mc_version;
+        if y > version.max_build_height() {
+            self.report_issue("above the build limit");
+            false
+        } else if y < version.min_build_height() {
+            self.report_issue("below the build limit");
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Sets the blocks covered by one block of a structure drawn at a scale of 1, the one
+    /// at the offset (dx, dz) from the structure's anchor at (x, z). At larger scales this
+    /// is a square of blocks, at smaller ones some offsets cover no block at all.
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_scaled_block(
+        &mut self,
+        block: Block,
+        x: i32,
+        y: i32,
+        z: i32,
+        dx: i32,
+        dz: i32,
+        override_whitelist: Option<&[Block]>,
+        override_blacklist: Option<&[Block]>,
+    ) {
+        for set_x in (x + self.scale_width(dx))..(x + self.scale_width(dx + 1)) {
+            for set_z in (z + self.scale_width(dz))..(z + self.scale_width(dz + 1)) {
+                self.set_block(
+                    block,
+                    set_x,
+                    y,
+                    set_z,
+                    override_whitelist,
+                    override_blacklist,
+                );
+            }
+        }
+    }
+
+    /// Sets the biome of the 4x4 column containing the given coordinates.
+    pub fn set_biome(&mut self, biome: Biome, x: i32, z: i32) {
+        // Check if coordinates are within bounds
+        if x < 0 || x > self.scale_factor_x as i32 || z < 0 || z > self.scale_factor_z as i32 {
+            return;
+        }
+
+        self.world.set_biome(x, z, biome);
+    }
+
+    /// Fills a cuboid area with the specified block between two coordinates.
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_blocks(
+        &mut self,
+        block: Block,
+        x1: i32,
+        y1: i32,
+        z1: i32,
+        x2: i32,
+        y2: i32,
+        z2: i32,
+        override_whitelist: Option<&[Block]>,
+        override_blacklist: Option<&[Block]>,
+    ) {
+        let (min_x, max_x) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
+        let (min_y, max_y) = if y1 < y2 { (y1, y2) } else { (y2, y1) };
+        let (min_z, max_z) = if z1 < z2 { (z1, z2) } else { (z2, z1) };
+
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                for z in min_z..=max_z {
+                    self.set_block(block, x, y, z, override_whitelist, override_blacklist);
+                }
+            }
+        }
+    }
+
+    /// Checks for a block at the given coordinates.
+    pub fn check_for_block(
+        &self,
+        x: i32,
+        y: i32,
+        z: i32,
+        whitelist: Option<&[Block]>,
+        blacklist: Option<&[Block]>,
+    ) -> bool {
+        // Retrieve the chunk modification map
+        if let Some(existing_block) = self.world.get_block(x, y, z) {
+            // Check against whitelist and blacklist
+            if let Some(whitelist) = whitelist {
+                if whitelist
+                    .iter()
+                    .any(|whitelisted_block: &Block| *whitelisted_block == existing_block)
+                {
+                    return true; // Block is in whitelist
+                }
+            }
+            if let Some(blacklist) = blacklist {
+                if blacklist
+                    .iter()
+                    .any(|blacklisted_block: &Block| *blacklisted_block == existing_block)
+                {
+                    return true; // Block is in blacklist
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Saves all changes made to the world by writing modified chunks to the appropriate region files.
+    /// Returns an error if the world could not be backed up or written.
+    pub fn save(&mut self) -> Result<(), String> {
+        println!("{} Saving world...", "[5/5]".bold());
+        emit_gui_progress_update(90.0, "Saving world...");
+
+        block_states::apply(&mut self.world);
+
+        let version: MinecraftVersion = self.args.mc_version;
+        for block in self.world.unavailable_blocks(version) {
+            println!(
+                "{} {} does not exist in Minecraft {}, using {} instead",
+                "Warning:".yellow().bold(),
+                block.name(),
+                version,
+                block.for_version(version).name_for(version)
+            );
+        }
+
+        let spawn: (i32, i32, i32) = self.spawn_point();
+        if self.args.bedrock {
+            bedrock::save(&self.world, self.args, spawn)?;
+        } else {
+            if !self.args.no_backup {
+                self.backup_world()?;
+            }
+            let is_new_world: bool = level_dat::is_new_world(Path::new(&self.args.path));
+            self.save_anvil();
+            level_dat::update(Path::new(&self.args.path), self.args, spawn, is_new_world)?;
+        }
+
+        if !self.args.export.is_empty() {
+            self.export_schematics();
+        }
+
+        if let Some(path) = &self.args.preview {
+            if let Err(e) = self.write_preview(Path::new(path)) {
+                eprintln!("{}", format!("Error! {}", e).red().bold());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prints the blocks that were placed and what saving them would write to disk.
+    pub fn print_summary(&self) {
+        let mut block_counts: Vec<(Block, u64)> = self.world.block_counts().into_iter().collect();
+        block_counts.sort_by_key(|(block, count)| (std::cmp::Reverse(*count), *block));
+
+        let total_blocks: u64 = block_counts.iter().map(|(_, count)| count).sum();
+        println!("Blocks placed: {}", total_blocks);
+        for (block, count) in &block_counts {
+            println!("  {:<32} {}", block.name(), count);
+        }
+
+        let regions: usize = self.world.regions.len();
+        let chunks: usize = self
+            .world
+            .regions
+            .values()
+            .map(|region: &RegionToModify| region.chunks.len())
+            .sum();
+        println!(
+            "Regions to write: {} ({} chunks with blocks)",
+            regions, chunks
+        );
+
+        if !self.args.bedrock {
+            // Every region file has a header and is written in full, empty chunks take one
+            // sector and chunks with blocks usually about three
+            const SECTOR_SIZE: u64 = 4096;
+            let empty_chunks: u64 = (regions * 1024 - chunks) as u64;
+            let estimated_size: u64 = regions as u64 * 2 * SECTOR_SIZE
+                + empty_chunks * SECTOR_SIZE
+                + chunks as u64 * 3 * SECTOR_SIZE;
+            println!(
+                "Estimated size of the region files: {:.1} MB",
+                estimated_size as f64 / 1_000_000.0
+            );
+        }
+    }
+
+    /// Renders the generated area from above to a PNG image and shows it in the GUI.
+    fn write_preview(&self, path: &Path) -> Result<(), String> {
+        println!("Rendering preview to {}...", path.display());
+
+        let png_data: Vec<u8> = preview::render(
+            &self.world,
+            self.scale_factor_x as u32 + 1,
+            self.scale_factor_z as u32 + 1,
+            self.args.preview_shading,
+        )?;
+        fs::write(path, &png_data).map_err(|e| format!("Failed to write preview image: {}", e))?;
+        emit_gui_preview(&png_data);
+
+        Ok(())
+    }
+
+    /// Spawn point given in the arguments or the center of the generated area,
+    /// at a height where the player can stand.
+    fn spawn_point(&self) -> (i32, i32, i32) {
+        let (x, z) = self.args.spawn_point.unwrap_or((
+            self.scale_factor_x as i32 / 2,
+            self.scale_factor_z as i32 / 2,
+        ));
+        let y: i32 = self.standing_height(x, z);
+
+        (x, y, z)
+    }
+
+    /// Backs up level.dat and every region file, entities file, data pack, index and
+    /// preview the generation will write. Returns an error if that failed, in which case
+    /// nothing must be written.
+    fn backup_world(&self) -> Result<(), String> {
+        let world_dir: &Path = Path::new(&self.args.path);
+        let mut paths: Vec<PathBuf> = vec![
+            PathBuf::from("level.dat"),
+            PathBuf::from(INDEX_FILE),
+            Path::new("datapacks").join(datapack::NAMESPACE),
+        ];
+        for ((region_x, region_z), region) in &self.world.regions {
+            let file_name: String = format!("r.{}.{}.mca", region_x, region_z);
+            paths.push(Path::new("region").join(&file_name));
+            if region
+                .chunks
+                .values()
+                .any(|chunk: &ChunkToModify| !chunk.entities.is_empty())
+            {
+                paths.push(Path::new("entities").join(&file_name));
+            }
+        }
+        if let Some(preview) = &self.args.preview {
+            if let Ok(relative) = Path::new(preview).strip_prefix(world_dir) {
+                paths.push(relative.to_path_buf());
+            }
+        }
+
+        let backup_id: String = backup::create(world_dir, &paths)
+            .map_err(|e| format!("{}, the world was not changed", e))?;
+        println!(
+            "Backed up the world as {}, undo with: arnis restore \"{}\" {}",
+            backup_id, self.args.path, backup_id
+        );
+        Ok(())
+    }
+
+    /// Writes all modified chunks to the Java Edition region files.
+    fn save_anvil(&mut self) {
+        let _debug: bool = self.args.debug;
+        let total_regions: u64 = self.world.regions.len() as u64;
+
+        let save_pb: ProgressBar = ProgressBar::new(total_regions);
+        save_pb.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "{spinner:.green} [{elapsed_precise}] [{bar:45}] {pos}/{len} regions ({eta})",
+                )
+                .unwrap()
+                .progress_chars("█▓░"),
+        );
+
+        let total_steps: f64 = 9.0;
+        let progress_increment_save: f64 = total_steps / total_regions as f64;
+        let mut current_progress_save: f64 = 90.0;
+        let mut last_emitted_progress: f64 = current_progress_save;
+
+        let empty_chunk: ChunkToModify = ChunkToModify::default();
+
+        for ((region_x, region_z), region_to_modify) in &self.world.regions {
+            let mut region: Region<File> = self.create_region(*region_x, *region_z);
+            let light: RegionLight = RegionLight::compute(&self.world, *region_x, *region_z);
+
+            for chunk_x in 0..32 {
+                for chunk_z in 0..32 {
+                    let chunk_x_abs: i32 = chunk_x + region_x * 32;
+                    let chunk_z_abs: i32 = chunk_z + region_z * 32;
+
+                    // Chunks without changes are written empty so the game does not generate them
+                    let chunk_to_modify: Option<&ChunkToModify> =
+                        region_to_modify.get_chunk(chunk_x, chunk_z);
+                    let source: &ChunkToModify = chunk_to_modify.unwrap_or(&empty_chunk);
+
+                    let mut chunk: Chunk = Chunk::empty(
+                        chunk_x_abs,
+                        chunk_z_abs,
+                        self.args.mc_version,
+                        &source.biome_columns(self.args.winter),
+                    );
+                    chunk.replace_sections(source.sections(
+                        light.chunk(chunk_x, chunk_z),
+                        self.args.winter,
+                        self.args.mc_version,
+                    ));
+                    chunk.other.insert(
+                        "block_entities".to_string(),
+                        Value::List(source.block_entities_nbt(
+                            chunk_x_abs,
+                            chunk_z_abs,
+                            self.args.mc_version,
+                        )),
+                    );
+                    chunk.other.insert(
+                        "Heightmaps".to_string(),
+                        ChunkHeightmaps::compute(source).to_nbt(),
+                    );
+                    // Light has been precomputed for the sections of modified chunks,
+                    // except where it depends on chunks outside the region
+                    chunk.is_light_on = u8::from(light.is_complete(chunk_x, chunk_z));
+
+                    let ser: Vec<u8> = fastnbt::to_bytes(&chunk).unwrap();
+
+                    // Write chunk data back to the correct location, ensuring correct chunk coordinates
+                    let expected_chunk_location: (usize, usize) =
+                        ((chunk_x as usize) & 31, (chunk_z as usize) & 31);
+                    region
+                        .write_chunk(expected_chunk_location.0, expected_chunk_location.1, &ser)
+                        .unwrap();
+                }
+            }
+
+            self.save_entities(*region_x, *region_z, region_to_modify);
+
+            save_pb.inc(1);
+
+            current_progress_save += progress_increment_save;
+            if (current_progress_save - last_emitted_progress).abs() > 0.25 {
+                emit_gui_progress_update(current_progress_save, "Saving world...");
+                last_emitted_progress = current_progress_save;
+            }
+        }
+
+        save_pb.finish();
+    }
+
+    /// Writes the generated area to the schematic files requested with `--export`,
+    /// picking the format from the file extension.
+    fn export_schematics(&self) {
+        let mut exports: Vec<(&Path, SchematicFormat)> = vec![];
+        for path in &self.args.export {
+            let path: &Path = Path::new(path);
+            match SchematicFormat::from_path(path) {
+                Ok(format) => exports.push((path, format)),
+                Err(e) => eprintln!("{}", format!("Error! {}", e).red().bold()),
+            }
+        }
+        if exports.is_empty() {
+            return;
+        }
+
+        let formats: Vec<SchematicFormat> = exports.iter().map(|(_, format)| *format).collect();
+        let volume: Volume = match Volume::from_world(&self.world, self.args.mc_version, &formats) {
+            Ok(Some(volume)) => volume,
+            Ok(None) => {
+                eprintln!("No blocks were generated, skipping schematic export");
+                return;
+            }
+            Err(e) => {
+                eprintln!("{}", format!("Error! {}", e).red().bold());
+                return;
+            }
+        };
+
+        for (path, format) in exports {
+            println!("Exporting schematic to {}...", path.display());
+            if let Err(e) = volume.write(format, path) {
+                eprintln!("{}", format!("Error! {}", e).red().bold());
+            }
+        }
+    }
+}
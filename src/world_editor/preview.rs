@@ -0,0 +1,111 @@
+use super::{SectionToModify, WorldToModify};
+use crate::block_definitions::Block;
+use crate::colors::RGBTuple;
+
+/// Brightness of a column that is higher, level with or lower than the one north of it,
+/// as on in-game maps.
+const SHADE_HIGHER: u16 = 255;
+const SHADE_LEVEL: u16 = 220;
+const SHADE_LOWER: u16 = 180;
+
+/// Renders the area from (0, 0) to (width - 1, depth - 1) seen from above as a PNG image,
+/// one pixel per block column in the map color of its topmost block. Columns without
+/// blocks stay transparent. With shading, slopes are lit like on in-game maps.
+pub fn render(
+    world: &WorldToModify,
+    width: u32,
+    depth: u32,
+    shading: bool,
+) -> Result<Vec<u8>, String> {
+    let mut columns: Vec<Option<(RGBTuple, i32)>> = vec![None; (width * depth) as usize];
+
+    for chunk_x in 0..=((width as i32 - 1) >> 4) {
+        for chunk_z in 0..=((depth as i32 - 1) >> 4) {
+            let Some(chunk) = world
+                .get_region(chunk_x >> 5, chunk_z >> 5)
+                .and_then(|region| region.get_chunk(chunk_x & 31, chunk_z & 31))
+            else {
+                continue;
+            };
+
+            // Sections from the top down, sorted once for all columns of the chunk
+            let mut sections: Vec<(&i8, &SectionToModify)> = chunk.sections.iter().collect();
+            sections.sort_by_key(|(y, _)| -**y);
+
+            for local_x in 0..16u8 {
+                for local_z in 0..16u8 {
+                    let x: u32 = (chunk_x * 16) as u32 + u32::from(local_x);
+                    let z: u32 = (chunk_z * 16) as u32 + u32::from(local_z);
+                    if x < width && z < depth {
+                        columns[(z * width + x) as usize] =
+                            top_block_color(&sections, local_x, local_z);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut pixels: Vec<u8> = Vec::with_capacity(columns.len() * 4);
+    for z in 0..depth {
+        for x in 0..width {
+            let Some((color, y)) = columns[(z * width + x) as usize] else {
+                pixels.extend_from_slice(&[0, 0, 0, 0]);
+                continue;
+            };
+
+            let shade: u16 = if shading && z > 0 {
+                match columns[((z - 1) * width + x) as usize] {
+                    Some((_, north_y)) if y > north_y => SHADE_HIGHER,
+                    Some((_, north_y)) if y < north_y => SHADE_LOWER,
+                    _ => SHADE_LEVEL,
+                }
+            } else if shading {
+                SHADE_LEVEL
+            } else {
+                255
+            };
+
+            let (r, g, b) = color;
+            pixels.extend_from_slice(&[
+                (u16::from(r) * shade / 255) as u8,
+                (u16::from(g) * shade / 255) as u8,
+                (u16::from(b) * shade / 255) as u8,
+                255,
+            ]);
+        }
+    }
+
+    encode_png(&pixels, width, depth)
+}
+
+/// Map color and height of the highest block in the column that shows on maps.
+fn top_block_color(sections: &[(&i8, &SectionToModify)], x: u8, z: u8) -> Option<(RGBTuple, i32)> {
+    sections.iter().find_map(|(section_y, section)| {
+        (0..16u8).rev().find_map(|y: u8| {
+            let color: RGBTuple = section
+                .get_block(x, y, z)
+                .and_then(|block: Block| block.map_color())?;
+            Some((color, i32::from(**section_y) * 16 + i32::from(y)))
+        })
+    })
+}
+
+fn encode_png(pixels: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let mut png_data: Vec<u8> = Vec::new();
+
+    let mut encoder: png::Encoder<&mut Vec<u8>> = png::Encoder::new(&mut png_data, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer: png::Writer<&mut Vec<u8>> = encoder
+        .write_header()
+        .map_err(|e| format!("Failed to encode preview image: {}", e))?;
+    writer
+        .write_image_data(pixels)
+        .map_err(|e| format!("Failed to encode preview image: {}", e))?;
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to encode preview image: {}", e))?;
+
+    Ok(png_data)
+}
@@ -0,0 +1,386 @@
+use super::block_states::BlockProperties;
+use super::{SectionToModify, WorldToModify};
+use crate::block_definitions::Block;
+use crate::minecraft_version::MinecraftVersion;
+use fastnbt::{ByteArray, IntArray, LongArray, Value};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use fnv::FnvHashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Block position relative to the origin of the volume.
+type BlockPos = (i32, i32, i32);
+
+/// The generated blocks cut out of the world as a single box, shared by the schematic writers.
+/// Blocks are indexed as `(y * length + z) * width + x`, which both formats use.
+pub struct Volume {
+    version: MinecraftVersion,
+    origin: (i32, i32, i32),
+    size: (i32, i32, i32),
+    /// Block states in the volume. Air is always the first entry.
+    palette: Vec<(Block, BTreeMap<String, String>)>,
+    blocks: Vec<u32>,
+    /// Block entities with their position relative to the origin
+    block_entities: Vec<(BlockPos, HashMap<String, Value>)>,
+}
+
+/// File formats the volume can be written in.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SchematicFormat {
+    /// Sponge schematic (version 3), as read by WorldEdit
+    Sponge,
+    /// Single-region Litematica schematic
+    Litematic,
+}
+
+impl SchematicFormat {
+    /// Picks the format from the file extension.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("schem") => Ok(Self::Sponge),
+            Some("litematic") => Ok(Self::Litematic),
+            _ => Err(format!("Unsupported schematic format: {}", path.display())),
+        }
+    }
+
+    /// Checks that a box of the given size fits in a file of this format.
+    fn check_size(&self, size: (i32, i32, i32)) -> Result<(), String> {
+        let fits: bool = match self {
+            // Each side is stored as an unsigned short
+            Self::Sponge => [size.0, size.1, size.2]
+                .iter()
+                .all(|side: &i32| u16::try_from(*side).is_ok()),
+            // The block count is stored as an int
+            Self::Litematic => i64::from(size.0)
+                .checked_mul(i64::from(size.1))
+                .and_then(|area: i64| area.checked_mul(i64::from(size.2)))
+                .is_some_and(|volume: i64| i32::try_from(volume).is_ok()),
+        };
+        if fits {
+            Ok(())
+        } else {
+            Err(format!(
+                "The generated area of {}x{}x{} blocks is too large for a {} schematic",
+                size.0,
+                size.1,
+                size.2,
+                match self {
+                    Self::Sponge => "Sponge",
+                    Self::Litematic => "Litematica",
+                }
+            ))
+        }
+    }
+}
+
+impl Volume {
+    /// Collects every block placed in the world, as it exists in the given version, after
+    /// checking that the area fits in each of the given formats.
+    /// Returns `None` if nothing was placed.
+    pub fn from_world(
+        world: &WorldToModify,
+        version: MinecraftVersion,
+        formats: &[SchematicFormat],
+    ) -> Result<Option<Self>, String> {
+        let Some((min, max)) = world.bounds() else {
+            return Ok(None);
+        };
+        let size: (i32, i32, i32) = (max.0 - min.0 + 1, max.1 - min.1 + 1, max.2 - min.2 + 1);
+        for format in formats {
+            format.check_size(size)?;
+        }
+
+        // Fail instead of aborting when the blocks do not fit in memory
+        let too_large = || -> String {
+            format!(
+                "Not enough memory to export the generated area of {}x{}x{} blocks",
+                size.0, size.1, size.2
+            )
+        };
+        let volume: usize = (size.0 as usize)
+            .checked_mul(size.1 as usize)
+            .and_then(|area: usize| area.checked_mul(size.2 as usize))
+            .ok_or_else(too_large)?;
+        let mut blocks: Vec<u32> = vec![];
+        blocks.try_reserve_exact(volume).map_err(|_| too_large())?;
+
+        let mut palette: Vec<(Block, BTreeMap<String, String>)> =
+            vec![(Block::Air, BTreeMap::new())];
+        let mut palette_lookup: FnvHashMap<(Block, Option<&BlockProperties>), u32> =
+            FnvHashMap::default();
+        palette_lookup.insert((Block::Air, None), 0);
+
+        for y in min.1..=max.1 {
+            for z in min.2..=max.2 {
+                for x in min.0..=max.0 {
+                    let (block, properties) = world.get_state(x, y, z);
+                    let state: (Block, Option<&BlockProperties>) =
+                        (block.for_version(version), properties);
+                    let index: u32 = *palette_lookup.entry(state).or_insert_with(|| {
+                        palette
+                            .push((state.0, SectionToModify::state_properties(state.0, state.1)));
+                        (palette.len() - 1) as u32
+                    });
+                    blocks.push(index);
+                }
+            }
+        }
+
+        let block_entities: Vec<(BlockPos, HashMap<String, Value>)> = world
+            .block_entities(version)
+            .into_iter()
+            .filter_map(|((x, y, z), nbt)| match nbt {
+                Value::Compound(nbt) => Some(((x - min.0, y - min.1, z - min.2), nbt)),
+                _ => None,
+            })
+            .collect();
+
+        Ok(Some(Self {
+            version,
+            origin: min,
+            size,
+            palette,
+            blocks,
+            block_entities,
+        }))
+    }
+
+    /// Writes the volume in the given format.
+    pub fn write(&self, format: SchematicFormat, path: &Path) -> Result<(), String> {
+        match format {
+            SchematicFormat::Sponge => self.write_sponge(path),
+            SchematicFormat::Litematic => self.write_litematic(path),
+        }
+    }
+
+    /// Writes the volume as a Sponge schematic (version 3), as read by WorldEdit.
+    fn write_sponge(&self, path: &Path) -> Result<(), String> {
+        let palette: HashMap<String, Value> = self
+            .palette
+            .iter()
+            .enumerate()
+            .map(|(index, (block, properties))| {
+                let mut state: String = block.id_for(self.version);
+                if !properties.is_empty() {
+                    let properties: Vec<String> = properties
+                        .iter()
+                        .map(|(key, value)| format!("{}={}", key, value))
+                        .collect();
+                    state.push_str(&format!("[{}]", properties.join(",")));
+                }
+                (state, Value::Int(index as i32))
+            })
+            .collect();
+
+        // Palette indices are stored as varints
+        let mut data: Vec<i8> = Vec::with_capacity(self.blocks.len());
+        for block in &self.blocks {
+            let mut value: u32 = *block;
+            while value >= 0x80 {
+                data.push(((value & 0x7F) | 0x80) as u8 as i8);
+                value >>= 7;
+            }
+            data.push(value as u8 as i8);
+        }
+
+        let block_entities: Vec<Value> = self
+            .block_entities
+            .iter()
+            .map(|((x, y, z), nbt)| {
+                let mut data: HashMap<String, Value> = nbt.clone();
+                let id: Value = data.remove("id").unwrap_or(Value::String(String::new()));
+                for key in ["x", "y", "z", "keepPacked"] {
+                    data.remove(key);
+                }
+
+                let mut entity: HashMap<String, Value> = HashMap::new();
+                entity.insert(
+                    "Pos".to_string(),
+                    Value::IntArray(IntArray::new(vec![*x, *y, *z])),
+                );
+                entity.insert("Id".to_string(), id);
+                entity.insert("Data".to_string(), Value::Compound(data));
+                Value::Compound(entity)
+            })
+            .collect();
+
+        let mut blocks: HashMap<String, Value> = HashMap::new();
+        blocks.insert("Palette".to_string(), Value::Compound(palette));
+        blocks.insert("Data".to_string(), Value::ByteArray(ByteArray::new(data)));
+        blocks.insert("BlockEntities".to_string(), Value::List(block_entities));
+
+        let mut metadata: HashMap<String, Value> = HashMap::new();
+        metadata.insert("Name".to_string(), Value::String(Self::name(path)));
+        metadata.insert("Date".to_string(), Value::Long(Self::timestamp()));
+
+        let mut schematic: HashMap<String, Value> = HashMap::new();
+        schematic.insert("Version".to_string(), Value::Int(3));
+        schematic.insert(
+            "DataVersion".to_string(),
+            Value::Int(self.version.data_version()),
+        );
+        // Sides are unsigned shorts, checked to fit when the volume was collected
+        schematic.insert("Width".to_string(), Value::Short(self.size.0 as u16 as i16));
+        schematic.insert(
+            "Height".to_string(),
+            Value::Short(self.size.1 as u16 as i16),
+        );
+        schematic.insert(
+            "Length".to_string(),
+            Value::Short(self.size.2 as u16 as i16),
+        );
+        schematic.insert(
+            "Offset".to_string(),
+            Value::IntArray(IntArray::new(vec![
+                self.origin.0,
+                self.origin.1,
+                self.origin.2,
+            ])),
+        );
+        schematic.insert("Metadata".to_string(), Value::Compound(metadata));
+        schematic.insert("Blocks".to_string(), Value::Compound(blocks));
+
+        let mut root: HashMap<String, Value> = HashMap::new();
+        root.insert("Schematic".to_string(), Value::Compound(schematic));
+
+        Self::write_compressed(path, &Value::Compound(root))
+    }
+
+    /// Writes the volume as a single-region Litematica schematic.
+    fn write_litematic(&self, path: &Path) -> Result<(), String> {
+        let palette: Vec<Value> = self
+            .palette
+            .iter()
+            .map(|(block, properties)| {
+                let mut state: HashMap<String, Value> = HashMap::new();
+                state.insert(
+                    "Name".to_string(),
+                    Value::String(block.id_for(self.version)),
+                );
+                if !properties.is_empty() {
+                    state.insert(
+                        "Properties".to_string(),
+                        Value::Compound(
+                            properties
+                                .iter()
+                                .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+                                .collect(),
+                        ),
+                    );
+                }
+                Value::Compound(state)
+            })
+            .collect();
+
+        // Entries are packed tightly and may span two longs
+        let mut bits_per_entry: usize = 2;
+        while (1 << bits_per_entry) < self.palette.len() {
+            bits_per_entry += 1;
+        }
+        let mut block_states: Vec<i64> = vec![0; (self.blocks.len() * bits_per_entry).div_ceil(64)];
+        for (i, block) in self.blocks.iter().enumerate() {
+            let bit: usize = i * bits_per_entry;
+            let (long, offset) = (bit / 64, bit % 64);
+            block_states[long] |= (i64::from(*block)) << offset;
+            if offset + bits_per_entry > 64 {
+                block_states[long + 1] |= i64::from(*block) >> (64 - offset);
+            }
+        }
+
+        let tile_entities: Vec<Value> = self
+            .block_entities
+            .iter()
+            .map(|((x, y, z), nbt)| {
+                let mut entity: HashMap<String, Value> = nbt.clone();
+                entity.remove("keepPacked");
+                entity.insert("x".to_string(), Value::Int(*x));
+                entity.insert("y".to_string(), Value::Int(*y));
+                entity.insert("z".to_string(), Value::Int(*z));
+                Value::Compound(entity)
+            })
+            .collect();
+
+        let mut region: HashMap<String, Value> = HashMap::new();
+        region.insert("Position".to_string(), Self::vector((0, 0, 0)));
+        region.insert("Size".to_string(), Self::vector(self.size));
+        region.insert("BlockStatePalette".to_string(), Value::List(palette));
+        region.insert(
+            "BlockStates".to_string(),
+            Value::LongArray(LongArray::new(block_states)),
+        );
+        region.insert("TileEntities".to_string(), Value::List(tile_entities));
+        region.insert("Entities".to_string(), Value::List(vec![]));
+        region.insert("PendingBlockTicks".to_string(), Value::List(vec![]));
+        region.insert("PendingFluidTicks".to_string(), Value::List(vec![]));
+
+        let name: String = Self::name(path);
+        let mut regions: HashMap<String, Value> = HashMap::new();
+        regions.insert(name.clone(), Value::Compound(region));
+
+        let total_blocks: i32 = self.blocks.iter().filter(|block| **block != 0).count() as i32;
+        let mut metadata: HashMap<String, Value> = HashMap::new();
+        metadata.insert("Name".to_string(), Value::String(name));
+        metadata.insert("Author".to_string(), Value::String("Arnis".to_string()));
+        metadata.insert("Description".to_string(), Value::String(String::new()));
+        metadata.insert("RegionCount".to_string(), Value::Int(1));
+        metadata.insert("TotalBlocks".to_string(), Value::Int(total_blocks));
+        metadata.insert(
+            "TotalVolume".to_string(),
+            Value::Int(self.blocks.len() as i32),
+        );
+        metadata.insert("TimeCreated".to_string(), Value::Long(Self::timestamp()));
+        metadata.insert("TimeModified".to_string(), Value::Long(Self::timestamp()));
+        metadata.insert("EnclosingSize".to_string(), Self::vector(self.size));
+
+        let mut root: HashMap<String, Value> = HashMap::new();
+        root.insert("Version".to_string(), Value::Int(6));
+        root.insert("SubVersion".to_string(), Value::Int(1));
+        root.insert(
+            "MinecraftDataVersion".to_string(),
+            Value::Int(self.version.data_version()),
+        );
+        root.insert("Metadata".to_string(), Value::Compound(metadata));
+        root.insert("Regions".to_string(), Value::Compound(regions));
+
+        Self::write_compressed(path, &Value::Compound(root))
+    }
+
+    fn vector((x, y, z): (i32, i32, i32)) -> Value {
+        let mut vector: HashMap<String, Value> = HashMap::new();
+        vector.insert("x".to_string(), Value::Int(x));
+        vector.insert("y".to_string(), Value::Int(y));
+        vector.insert("z".to_string(), Value::Int(z));
+        Value::Compound(vector)
+    }
+
+    /// Schematic name taken from the file name.
+    fn name(path: &Path) -> String {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "arnis".to_string())
+    }
+
+    fn timestamp() -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or(0)
+    }
+
+    fn write_compressed(path: &Path, nbt: &Value) -> Result<(), String> {
+        let data: Vec<u8> =
+            fastnbt::to_bytes(nbt).map_err(|e| format!("Failed to serialize schematic: {}", e))?;
+
+        let file: File = File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let mut encoder: GzEncoder<File> = GzEncoder::new(file, Compression::default());
+        encoder
+            .write_all(&data)
+            .and_then(|_| encoder.finish().map(|_| ()))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}